@@ -0,0 +1,163 @@
+use crate::currency::Currency;
+use crate::money::Money;
+use crate::pricing::PricingStage;
+
+/// Category of a single line in a cost breakdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CostKind {
+    Filament,
+    Purge,
+    Electricity,
+    Wear,
+    Consumables,
+    MachineTime,
+    Labor,
+    FailureRisk,
+    Shipping,
+    PostProcessing,
+    Markup,
+    Discount,
+    SetupFee,
+    MinimumOrder,
+    Fees,
+    Rounding,
+    Tax,
+}
+
+impl CostKind {
+    /// Returns the pricing stage lines of this kind belong to.
+    pub fn stage(&self) -> PricingStage {
+        match self {
+            CostKind::Filament | CostKind::Purge => PricingStage::Materials,
+            CostKind::Electricity
+            | CostKind::Wear
+            | CostKind::Consumables
+            | CostKind::MachineTime
+            | CostKind::FailureRisk => PricingStage::Machine,
+            CostKind::Labor => PricingStage::Labor,
+            CostKind::PostProcessing => PricingStage::PostProcessing,
+            CostKind::Shipping => PricingStage::Shipping,
+            CostKind::Markup => PricingStage::Markup,
+            CostKind::Discount | CostKind::SetupFee | CostKind::MinimumOrder => {
+                PricingStage::Adjustments
+            }
+            CostKind::Fees => PricingStage::Fees,
+            CostKind::Rounding => PricingStage::Rounding,
+            CostKind::Tax => PricingStage::Tax,
+        }
+    }
+}
+
+/// A single labelled amount in a cost breakdown.
+#[derive(Clone, Debug)]
+pub struct CostLine {
+    pub kind: CostKind,
+    pub label: String,
+    pub amount: Money,
+}
+
+/// Itemised result of a cost calculation.
+///
+/// Every line is rounded to the currency's minor unit as it is added, and all totals are
+/// exact sums of lines, so the lines always add up to the totals shown.
+#[derive(Clone, Debug)]
+pub struct CostBreakdown {
+    pub currency: Currency,              // Currency all amounts are in
+    pub lines: Vec<CostLine>,            // Cost components and markup, in order
+    pub quantity: u32,                   // Number of parts the totals cover
+    pub machine_hours: f32,              // Printer running time of the job
+    pub attended_hours: f32,             // Hands-on labor and post-processing time
+    pub total_cost: Money,               // Sum of all cost components (excluding markup)
+    pub markup_base: Money,              // Cost components the markup is charged on
+    pub suggested_price: Money,          // Total cost with markup
+    pub minimum_order_adjustment: Money, // Amount added to reach the minimum order value
+    pub unrounded_price: Money,          // Quoted price before rounding
+    pub tax: Money,                      // Total tax on the suggested price
+    pub gross_price: Money,              // Suggested price including tax
+    pub tax_inclusive: bool,             // Whether the quote is given including tax
+}
+
+impl CostBreakdown {
+    /// Creates an empty breakdown for `quantity` parts priced in `currency`.
+    pub fn new(currency: Currency, quantity: u32) -> Self {
+        let zero = Money::zero(currency);
+        Self {
+            currency,
+            lines: Vec::new(),
+            quantity,
+            machine_hours: 0.0,
+            attended_hours: 0.0,
+            total_cost: zero,
+            markup_base: zero,
+            suggested_price: zero,
+            minimum_order_adjustment: zero,
+            unrounded_price: zero,
+            tax: zero,
+            gross_price: zero,
+            tax_inclusive: false,
+        }
+    }
+
+    /// Appends a line to the breakdown. The amount must be in the breakdown's currency.
+    pub fn push(&mut self, kind: CostKind, label: impl Into<String>, amount: Money) {
+        debug_assert_eq!(amount.currency(), self.currency, "line in a different currency");
+        self.lines.push(CostLine {
+            kind,
+            label: label.into(),
+            amount,
+        });
+    }
+
+    /// Returns the total cost of a single part.
+    pub fn unit_cost(&self) -> Money {
+        self.total_cost.per(self.quantity)
+    }
+
+    /// Returns the quoted price of a single part, gross or net depending on the tax mode.
+    pub fn unit_price(&self) -> Money {
+        self.quoted_price().per(self.quantity)
+    }
+
+    /// Returns the price left after tax and channel and payment fees.
+    pub fn net_revenue(&self) -> Money {
+        self.suggested_price - self.total_for(CostKind::Fees)
+    }
+
+    /// Returns the profit left from the net revenue after costs.
+    pub fn profit(&self) -> Money {
+        self.net_revenue() - self.total_cost
+    }
+
+    /// Returns the price quoted to the customer, gross or net depending on the tax mode.
+    pub fn quoted_price(&self) -> Money {
+        if self.tax_inclusive {
+            self.gross_price
+        } else {
+            self.suggested_price
+        }
+    }
+
+    /// Returns the summed amount of the lines matching a filter.
+    pub fn total_where(&self, filter: impl Fn(&CostLine) -> bool) -> Money {
+        self.lines
+            .iter()
+            .filter(|line| filter(line))
+            .fold(Money::zero(self.currency), |total, line| total + line.amount)
+    }
+
+    /// Returns the summed amount of all lines in the given pricing stage.
+    pub fn total_for_stage(&self, stage: PricingStage) -> Money {
+        self.total_where(|line| line.kind.stage() == stage)
+    }
+
+    /// Returns the summed amount of all lines of the given kind.
+    pub fn total_for(&self, kind: CostKind) -> Money {
+        self.total_where(|line| line.kind == kind)
+    }
+}
+
+impl Default for CostBreakdown {
+    fn default() -> Self {
+        Self::new(Currency::default(), 0)
+    }
+}
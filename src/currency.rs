@@ -0,0 +1,477 @@
+use std::fmt;
+
+use crate::money::Money;
+
+/// ISO 4217 data of a currency.
+struct CurrencyInfo {
+    code: &'static str,
+    minor_units: u32, // Decimal places of the minor unit (0 for JPY, 3 for KWD)
+    cash_step: i64,   // Smallest cash amount in minor units (5 for CHF, 100 for SEK)
+    symbol: &'static str,
+    name: &'static str,
+}
+
+const fn info(
+    code: &'static str,
+    minor_units: u32,
+    cash_step: i64,
+    symbol: &'static str,
+    name: &'static str,
+) -> CurrencyInfo {
+    CurrencyInfo { code, minor_units, cash_step, symbol, name }
+}
+
+// Active ISO 4217 currencies and fund codes, sorted by code. Codes without a minor unit
+// (precious metals, SDR and testing codes) are left out
+const CURRENCIES: &[CurrencyInfo] = &[
+    info("AED", 2, 1, "د.إ", "UAE Dirham"),
+    info("AFN", 2, 1, "؋", "Afghani"),
+    info("ALL", 2, 1, "L", "Lek"),
+    info("AMD", 2, 1, "֏", "Armenian Dram"),
+    info("ANG", 2, 1, "NAƒ", "Netherlands Antillean Guilder"),
+    info("AOA", 2, 1, "Kz", "Kwanza"),
+    info("ARS", 2, 1, "$", "Argentine Peso"),
+    info("AUD", 2, 5, "A$", "Australian Dollar"),
+    info("AWG", 2, 1, "Afl.", "Aruban Florin"),
+    info("AZN", 2, 1, "₼", "Azerbaijan Manat"),
+    info("BAM", 2, 1, "KM", "Convertible Mark"),
+    info("BBD", 2, 1, "Bds$", "Barbados Dollar"),
+    info("BDT", 2, 1, "৳", "Taka"),
+    info("BGN", 2, 1, "лв", "Bulgarian Lev"),
+    info("BHD", 3, 1, "BD", "Bahraini Dinar"),
+    info("BIF", 0, 1, "FBu", "Burundi Franc"),
+    info("BMD", 2, 1, "BD$", "Bermudian Dollar"),
+    info("BND", 2, 1, "B$", "Brunei Dollar"),
+    info("BOB", 2, 1, "Bs", "Boliviano"),
+    info("BOV", 2, 1, "BOV", "Mvdol"),
+    info("BRL", 2, 1, "R$", "Brazilian Real"),
+    info("BSD", 2, 1, "B$", "Bahamian Dollar"),
+    info("BTN", 2, 1, "Nu.", "Ngultrum"),
+    info("BWP", 2, 1, "P", "Pula"),
+    info("BYN", 2, 1, "Br", "Belarusian Ruble"),
+    info("BZD", 2, 1, "BZ$", "Belize Dollar"),
+    info("CAD", 2, 5, "C$", "Canadian Dollar"),
+    info("CDF", 2, 1, "FC", "Congolese Franc"),
+    info("CHE", 2, 1, "CHE", "WIR Euro"),
+    info("CHF", 2, 5, "CHF", "Swiss Franc"),
+    info("CHW", 2, 1, "CHW", "WIR Franc"),
+    info("CLF", 4, 1, "UF", "Unidad de Fomento"),
+    info("CLP", 0, 1, "$", "Chilean Peso"),
+    info("CNY", 2, 1, "¥", "Yuan Renminbi"),
+    info("COP", 2, 1, "$", "Colombian Peso"),
+    info("COU", 2, 1, "COU", "Unidad de Valor Real"),
+    info("CRC", 2, 1, "₡", "Costa Rican Colon"),
+    info("CUP", 2, 1, "$MN", "Cuban Peso"),
+    info("CVE", 2, 1, "Esc", "Cabo Verde Escudo"),
+    info("CZK", 2, 100, "Kč", "Czech Koruna"),
+    info("DJF", 0, 1, "Fdj", "Djibouti Franc"),
+    info("DKK", 2, 50, "kr.", "Danish Krone"),
+    info("DOP", 2, 1, "RD$", "Dominican Peso"),
+    info("DZD", 2, 1, "DA", "Algerian Dinar"),
+    info("EGP", 2, 1, "E£", "Egyptian Pound"),
+    info("ERN", 2, 1, "Nfk", "Nakfa"),
+    info("ETB", 2, 1, "Br", "Ethiopian Birr"),
+    info("EUR", 2, 1, "€", "Euro"),
+    info("FJD", 2, 1, "FJ$", "Fiji Dollar"),
+    info("FKP", 2, 1, "£", "Falkland Islands Pound"),
+    info("GBP", 2, 1, "£", "Pound Sterling"),
+    info("GEL", 2, 1, "₾", "Lari"),
+    info("GHS", 2, 1, "GH₵", "Ghana Cedi"),
+    info("GIP", 2, 1, "£", "Gibraltar Pound"),
+    info("GMD", 2, 1, "D", "Dalasi"),
+    info("GNF", 0, 1, "FG", "Guinean Franc"),
+    info("GTQ", 2, 1, "Q", "Quetzal"),
+    info("GYD", 2, 1, "G$", "Guyana Dollar"),
+    info("HKD", 2, 10, "HK$", "Hong Kong Dollar"),
+    info("HNL", 2, 1, "L", "Lempira"),
+    info("HTG", 2, 1, "G", "Gourde"),
+    info("HUF", 2, 500, "Ft", "Forint"),
+    info("IDR", 2, 1, "Rp", "Rupiah"),
+    info("ILS", 2, 10, "₪", "New Israeli Sheqel"),
+    info("INR", 2, 1, "₹", "Indian Rupee"),
+    info("IQD", 3, 1, "IQD", "Iraqi Dinar"),
+    info("IRR", 2, 1, "﷼", "Iranian Rial"),
+    info("ISK", 0, 1, "kr", "Iceland Krona"),
+    info("JMD", 2, 1, "J$", "Jamaican Dollar"),
+    info("JOD", 3, 1, "JD", "Jordanian Dinar"),
+    info("JPY", 0, 1, "¥", "Yen"),
+    info("KES", 2, 1, "KSh", "Kenyan Shilling"),
+    info("KGS", 2, 1, "сом", "Som"),
+    info("KHR", 2, 1, "៛", "Riel"),
+    info("KMF", 0, 1, "CF", "Comorian Franc"),
+    info("KPW", 2, 1, "₩", "North Korean Won"),
+    info("KRW", 0, 1, "₩", "Won"),
+    info("KWD", 3, 1, "KD", "Kuwaiti Dinar"),
+    info("KYD", 2, 1, "CI$", "Cayman Islands Dollar"),
+    info("KZT", 2, 1, "₸", "Tenge"),
+    info("LAK", 2, 1, "₭", "Lao Kip"),
+    info("LBP", 2, 1, "L£", "Lebanese Pound"),
+    info("LKR", 2, 1, "Rs", "Sri Lanka Rupee"),
+    info("LRD", 2, 1, "L$", "Liberian Dollar"),
+    info("LSL", 2, 1, "L", "Loti"),
+    info("LYD", 3, 1, "LD", "Libyan Dinar"),
+    info("MAD", 2, 1, "DH", "Moroccan Dirham"),
+    info("MDL", 2, 1, "L", "Moldovan Leu"),
+    info("MGA", 2, 1, "Ar", "Malagasy Ariary"),
+    info("MKD", 2, 1, "ден", "Denar"),
+    info("MMK", 2, 1, "K", "Kyat"),
+    info("MNT", 2, 1, "₮", "Tugrik"),
+    info("MOP", 2, 1, "MOP$", "Pataca"),
+    info("MRU", 2, 1, "UM", "Ouguiya"),
+    info("MUR", 2, 1, "Rs", "Mauritius Rupee"),
+    info("MVR", 2, 1, "Rf", "Rufiyaa"),
+    info("MWK", 2, 1, "MK", "Malawi Kwacha"),
+    info("MXN", 2, 1, "$", "Mexican Peso"),
+    info("MXV", 2, 1, "MXV", "Mexican Unidad de Inversion (UDI)"),
+    info("MYR", 2, 1, "RM", "Malaysian Ringgit"),
+    info("MZN", 2, 1, "MT", "Mozambique Metical"),
+    info("NAD", 2, 1, "N$", "Namibia Dollar"),
+    info("NGN", 2, 1, "₦", "Naira"),
+    info("NIO", 2, 1, "C$", "Cordoba Oro"),
+    info("NOK", 2, 100, "kr", "Norwegian Krone"),
+    info("NPR", 2, 1, "Rs", "Nepalese Rupee"),
+    info("NZD", 2, 10, "NZ$", "New Zealand Dollar"),
+    info("OMR", 3, 1, "RO", "Rial Omani"),
+    info("PAB", 2, 1, "B/.", "Balboa"),
+    info("PEN", 2, 1, "S/", "Sol"),
+    info("PGK", 2, 1, "K", "Kina"),
+    info("PHP", 2, 1, "₱", "Philippine Peso"),
+    info("PKR", 2, 1, "Rs", "Pakistan Rupee"),
+    info("PLN", 2, 1, "zł", "Zloty"),
+    info("PYG", 0, 1, "₲", "Guarani"),
+    info("QAR", 2, 1, "QR", "Qatari Rial"),
+    info("RON", 2, 1, "lei", "Romanian Leu"),
+    info("RSD", 2, 1, "дин", "Serbian Dinar"),
+    info("RUB", 2, 1, "₽", "Russian Ruble"),
+    info("RWF", 0, 1, "FRw", "Rwanda Franc"),
+    info("SAR", 2, 1, "SR", "Saudi Riyal"),
+    info("SBD", 2, 1, "SI$", "Solomon Islands Dollar"),
+    info("SCR", 2, 1, "SR", "Seychelles Rupee"),
+    info("SDG", 2, 1, "SDG", "Sudanese Pound"),
+    info("SEK", 2, 100, "kr", "Swedish Krona"),
+    info("SGD", 2, 5, "S$", "Singapore Dollar"),
+    info("SHP", 2, 1, "£", "Saint Helena Pound"),
+    info("SLE", 2, 1, "Le", "Leone"),
+    info("SOS", 2, 1, "Sh", "Somali Shilling"),
+    info("SRD", 2, 1, "$", "Surinam Dollar"),
+    info("SSP", 2, 1, "SSP", "South Sudanese Pound"),
+    info("STN", 2, 1, "Db", "Dobra"),
+    info("SVC", 2, 1, "₡", "El Salvador Colon"),
+    info("SYP", 2, 1, "£S", "Syrian Pound"),
+    info("SZL", 2, 1, "E", "Lilangeni"),
+    info("THB", 2, 1, "฿", "Baht"),
+    info("TJS", 2, 1, "SM", "Somoni"),
+    info("TMT", 2, 1, "m", "Turkmenistan New Manat"),
+    info("TND", 3, 1, "DT", "Tunisian Dinar"),
+    info("TOP", 2, 1, "T$", "Pa'anga"),
+    info("TRY", 2, 1, "₺", "Turkish Lira"),
+    info("TTD", 2, 1, "TT$", "Trinidad and Tobago Dollar"),
+    info("TWD", 2, 100, "NT$", "New Taiwan Dollar"),
+    info("TZS", 2, 1, "TSh", "Tanzanian Shilling"),
+    info("UAH", 2, 1, "₴", "Hryvnia"),
+    info("UGX", 0, 1, "USh", "Uganda Shilling"),
+    info("USD", 2, 1, "$", "US Dollar"),
+    info("USN", 2, 1, "$", "US Dollar (Next day)"),
+    info("UYI", 0, 1, "UYI", "Uruguay Peso en Unidades Indexadas (UI)"),
+    info("UYU", 2, 1, "$U", "Peso Uruguayo"),
+    info("UYW", 4, 1, "UYW", "Unidad Previsional"),
+    info("UZS", 2, 1, "soʻm", "Uzbekistan Sum"),
+    info("VED", 2, 1, "Bs.D", "Bolívar Soberano"),
+    info("VES", 2, 1, "Bs.S", "Bolívar Soberano"),
+    info("VND", 0, 1, "₫", "Dong"),
+    info("VUV", 0, 1, "VT", "Vatu"),
+    info("WST", 2, 1, "WS$", "Tala"),
+    info("XAF", 0, 1, "FCFA", "CFA Franc BEAC"),
+    info("XCD", 2, 1, "EC$", "East Caribbean Dollar"),
+    info("XCG", 2, 1, "Cg", "Caribbean Guilder"),
+    info("XOF", 0, 1, "CFA", "CFA Franc BCEAO"),
+    info("XPF", 0, 1, "₣", "CFP Franc"),
+    info("YER", 2, 1, "﷼", "Yemeni Rial"),
+    info("ZAR", 2, 10, "R", "Rand"),
+    info("ZMW", 2, 1, "ZK", "Zambian Kwacha"),
+    info("ZWG", 2, 1, "ZiG", "Zimbabwe Gold"),
+];
+
+/// An ISO 4217 currency, identified by its three-letter code.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency {
+    code: [u8; 3],
+}
+
+impl Currency {
+    pub const AUD: Currency = Currency::new("AUD");
+    pub const CHF: Currency = Currency::new("CHF");
+    pub const EUR: Currency = Currency::new("EUR");
+    pub const GBP: Currency = Currency::new("GBP");
+    pub const JPY: Currency = Currency::new("JPY");
+    pub const PLN: Currency = Currency::new("PLN");
+    pub const SEK: Currency = Currency::new("SEK");
+    pub const USD: Currency = Currency::new("USD");
+
+    /// Creates a currency from a code known to be in the table.
+    const fn new(code: &str) -> Self {
+        let bytes = code.as_bytes();
+        Self {
+            code: [bytes[0], bytes[1], bytes[2]],
+        }
+    }
+
+    /// Returns all supported currencies, sorted by code.
+    pub fn all() -> impl Iterator<Item = Currency> {
+        CURRENCIES.iter().map(|info| Currency::new(info.code))
+    }
+
+    /// Looks up a currency by its ISO 4217 code.
+    pub fn from_code(code: &str) -> Option<Currency> {
+        let code = code.trim();
+        CURRENCIES
+            .iter()
+            .find(|info| info.code.eq_ignore_ascii_case(code))
+            .map(|info| Currency::new(info.code))
+    }
+
+    fn info(&self) -> &'static CurrencyInfo {
+        CURRENCIES
+            .iter()
+            .find(|info| info.code.as_bytes() == self.code)
+            .expect("currencies are only created from the table")
+    }
+
+    /// Returns the ISO 4217 code of the currency.
+    pub fn code(&self) -> &'static str {
+        self.info().code
+    }
+
+    /// Returns the English name of the currency.
+    pub fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    /// Returns the currency symbol.
+    pub fn symbol(&self) -> &'static str {
+        self.info().symbol
+    }
+
+    /// Returns the number of decimal places of the currency's minor unit.
+    pub fn minor_units(&self) -> u32 {
+        self.info().minor_units
+    }
+
+    /// Returns the smallest amount payable in cash, in minor units, for currencies whose
+    /// smallest coins are worth more than one minor unit.
+    pub fn cash_step(&self) -> i64 {
+        self.info().cash_step
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::EUR
+    }
+}
+
+impl fmt::Debug for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// How a locale writes numbers and amounts of money.
+#[derive(Clone, Copy, PartialEq)]
+pub struct NumberLocale {
+    pub name: &'static str,
+    pub decimal_separator: char,
+    pub thousands_separator: char,
+    pub symbol_first: bool, // Whether the currency symbol goes before the amount
+    pub symbol_space: bool, // Whether a space separates the symbol and the amount
+}
+
+const fn locale(
+    name: &'static str,
+    decimal_separator: char,
+    thousands_separator: char,
+    symbol_first: bool,
+    symbol_space: bool,
+) -> NumberLocale {
+    NumberLocale { name, decimal_separator, thousands_separator, symbol_first, symbol_space }
+}
+
+/// Locales offered for formatting, the first being the default.
+pub const LOCALES: [NumberLocale; 10] = [
+    locale("English (US)", '.', ',', true, false),
+    locale("English (UK)", '.', ',', true, false),
+    locale("English (Australia)", '.', ',', true, false),
+    locale("German", ',', '.', false, true),
+    locale("French", ',', '\u{202f}', false, true),
+    locale("Dutch", ',', '.', true, true),
+    locale("Swiss", '.', '\'', true, true),
+    locale("Swedish", ',', '\u{a0}', false, true),
+    locale("Polish", ',', '\u{a0}', false, true),
+    locale("Japanese", '.', ',', true, false),
+];
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        LOCALES[0]
+    }
+}
+
+impl NumberLocale {
+    /// Groups the digits of a whole number with the thousands separator.
+    fn group(&self, digits: &str) -> String {
+        let mut grouped = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push(self.thousands_separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    /// Formats an amount with the currency's minor units, digit grouping and symbol.
+    pub fn format_money(&self, money: Money) -> String {
+        let currency = money.currency();
+        let decimals = currency.minor_units();
+        let scale = 10i64.pow(decimals);
+        let units = money.minor_units();
+        let mut number = self.group(&(units.abs() / scale).to_string());
+        if decimals > 0 {
+            let minor = units.abs() % scale;
+            number.push(self.decimal_separator);
+            number.push_str(&format!("{:0width$}", minor, width = decimals as usize));
+        }
+        let sign = if units < 0 { "-" } else { "" };
+        let space = if self.symbol_space { "\u{a0}" } else { "" };
+        if self.symbol_first {
+            format!("{}{}{}{}", sign, currency.symbol(), space, number)
+        } else {
+            format!("{}{}{}{}", sign, number, space, currency.symbol())
+        }
+    }
+
+    /// Formats a number for an input field, with the locale's decimal separator and
+    /// without digit grouping.
+    pub fn format_input(&self, value: f64, decimals: std::ops::RangeInclusive<usize>) -> String {
+        eframe::emath::format_with_decimals_in_range(value, decimals)
+            .replace('.', &self.decimal_separator.to_string())
+    }
+
+    /// Parses a number typed with a decimal comma or point and optional digit grouping.
+    /// A single separator that is not the locale's decimal separator counts as grouping
+    /// only in the form "1,234": exactly three digits after it and a whole part that
+    /// does not start with zero. So "1,5" and "0,125" are decimals in any locale, while
+    /// "1,234" is 1234 where the point is the decimal separator. A separator that appears
+    /// more than once, as in "1.234.567", is always grouping.
+    pub fn parse_number(&self, text: &str) -> Option<f64> {
+        let cleaned: String = text
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '\u{a0}' && *c != '\u{202f}' && *c != '\'')
+            .collect();
+        let decimal = match (cleaned.rfind('.'), cleaned.rfind(',')) {
+            (None, None) => None,
+            (Some(point), Some(comma)) => Some(if point > comma { '.' } else { ',' }),
+            (Some(position), None) | (None, Some(position)) => {
+                let separator = if cleaned[position..].starts_with('.') { '.' } else { ',' };
+                let single = cleaned.matches(separator).count() == 1;
+                let digits_after = cleaned.len() - position - 1;
+                let whole = cleaned[..position].trim_start_matches(['-', '+']);
+                let grouping =
+                    !single || (digits_after == 3 && !whole.is_empty() && !whole.starts_with('0'));
+                (single && (separator == self.decimal_separator || !grouping)).then_some(separator)
+            }
+        };
+        let normalized: String = cleaned
+            .chars()
+            .filter(|c| !matches!(c, '.' | ',') || Some(*c) == decimal)
+            .map(|c| if Some(c) == decimal { '.' } else { c })
+            .collect();
+        normalized.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale_named(name: &str) -> NumberLocale {
+        *LOCALES.iter().find(|locale| locale.name == name).unwrap()
+    }
+
+    #[test]
+    fn parses_decimal_points_and_commas() {
+        let us = locale_named("English (US)");
+        let german = locale_named("German");
+        assert_eq!(us.parse_number("1.5"), Some(1.5));
+        assert_eq!(us.parse_number("1,5"), Some(1.5));
+        assert_eq!(german.parse_number("1,5"), Some(1.5));
+        assert_eq!(german.parse_number("1.5"), Some(1.5));
+        assert_eq!(us.parse_number("-2,75"), Some(-2.75));
+    }
+
+    #[test]
+    fn reads_three_digits_after_a_foreign_separator_as_grouping() {
+        let us = locale_named("English (US)");
+        let german = locale_named("German");
+        assert_eq!(us.parse_number("1,234"), Some(1234.0));
+        assert_eq!(german.parse_number("1.234"), Some(1234.0));
+        assert_eq!(us.parse_number("1.234"), Some(1.234));
+        assert_eq!(german.parse_number("1,234"), Some(1.234));
+    }
+
+    #[test]
+    fn reads_a_separator_after_a_leading_zero_as_decimal() {
+        let us = locale_named("English (US)");
+        assert_eq!(us.parse_number("0,125"), Some(0.125));
+        assert_eq!(us.parse_number(",125"), Some(0.125));
+        assert_eq!(us.parse_number("-0,125"), Some(-0.125));
+    }
+
+    #[test]
+    fn parses_grouped_numbers() {
+        let us = locale_named("English (US)");
+        let german = locale_named("German");
+        let swiss = locale_named("Swiss");
+        let french = locale_named("French");
+        assert_eq!(us.parse_number("1,234,567.89"), Some(1_234_567.89));
+        assert_eq!(german.parse_number("1.234.567,89"), Some(1_234_567.89));
+        assert_eq!(us.parse_number("1.234.567"), Some(1_234_567.0));
+        assert_eq!(swiss.parse_number("1'234.5"), Some(1234.5));
+        assert_eq!(french.parse_number("1\u{202f}234,5"), Some(1234.5));
+        assert_eq!(us.parse_number("abc"), None);
+    }
+
+    #[test]
+    fn formats_money_per_locale() {
+        let amount = Money::from_f64(1234.5, Currency::EUR);
+        assert_eq!(locale_named("English (US)").format_money(amount), "€1,234.50");
+        assert_eq!(locale_named("German").format_money(amount), "1.234,50\u{a0}€");
+        assert_eq!(locale_named("English (US)").format_money(-amount), "-€1,234.50");
+    }
+
+    #[test]
+    fn formats_money_with_the_minor_units_of_the_currency() {
+        let us = locale_named("English (US)");
+        let kwd = Currency::from_code("KWD").unwrap();
+        assert_eq!(us.format_money(Money::from_f64(1234.0, Currency::JPY)), "¥1,234");
+        assert_eq!(us.format_money(Money::from_f64(1.5, kwd)), "KD1.500");
+        assert_eq!(us.format_money(Money::from_f64(0.05, Currency::EUR)), "€0.05");
+    }
+
+    #[test]
+    fn looks_up_currencies_by_code() {
+        assert_eq!(Currency::from_code(" chf "), Some(Currency::CHF));
+        assert_eq!(Currency::from_code("XYZ"), None);
+        assert_eq!(Currency::CHF.cash_step(), 5);
+        assert_eq!(Currency::JPY.minor_units(), 0);
+        assert_eq!(Currency::from_code("XOF").map(|c| c.minor_units()), Some(0));
+        assert_eq!(Currency::from_code("LYD").map(|c| c.minor_units()), Some(3));
+        assert_eq!(Currency::from_code("CLF").map(|c| c.minor_units()), Some(4));
+    }
+
+    #[test]
+    fn lists_currencies_sorted_and_unique() {
+        let codes: Vec<&str> = Currency::all().map(|currency| currency.code()).collect();
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use crate::currency::Currency;
+
+/// Exchange rates relative to the base currency that stored prices and rates are
+/// entered in.
+pub struct ExchangeRates {
+    pub base: Currency,                // Currency of stored values
+    pub rates: HashMap<Currency, f32>, // Units of each currency per unit of the base
+}
+
+impl Default for ExchangeRates {
+    fn default() -> Self {
+        Self {
+            base: Currency::EUR,
+            rates: HashMap::from([
+                (Currency::USD, 1.08),
+                (Currency::GBP, 0.85),
+                (Currency::CHF, 0.95),
+                (Currency::SEK, 11.40),
+                (Currency::PLN, 4.30),
+                (Currency::JPY, 162.0),
+                (Currency::AUD, 1.63),
+            ]),
+        }
+    }
+}
+
+impl ExchangeRates {
+    /// Returns the units of `currency` per unit of the base currency, if known.
+    pub fn rate(&self, currency: Currency) -> Option<f32> {
+        if currency == self.base {
+            return Some(1.0);
+        }
+        self.rates.get(&currency).copied().filter(|rate| *rate > 0.0)
+    }
+
+    /// Converts an amount between two currencies, if both rates are known.
+    pub fn convert(&self, amount: f32, from: Currency, to: Currency) -> Option<f32> {
+        if from == to {
+            return Some(amount);
+        }
+        Some(amount / self.rate(from)? * self.rate(to)?)
+    }
+
+    /// Re-expresses the rates relative to a new base currency. Returns false and leaves
+    /// the table unchanged when the new base has no rate.
+    pub fn rebase(&mut self, base: Currency) -> bool {
+        let Some(base_rate) = self.rate(base) else {
+            return false;
+        };
+        let old_base = self.base;
+        let mut rates: HashMap<Currency, f32> = self
+            .rates
+            .iter()
+            .filter(|(currency, _)| **currency != base)
+            .map(|(currency, rate)| (*currency, rate / base_rate))
+            .collect();
+        if old_base != base {
+            rates.insert(old_base, 1.0 / base_rate);
+        }
+        self.base = base;
+        self.rates = rates;
+        true
+    }
+
+    /// Loads rates from an ECB-format XML file or a CSV file, chosen by the content.
+    pub fn load_file(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+        if text.trim_start().starts_with('<') {
+            Self::parse_ecb_xml(&text)
+        } else {
+            Self::parse_csv(&text)
+        }
+    }
+
+    /// Parses the ECB daily reference rates XML (`<Cube currency='USD' rate='1.08'/>`),
+    /// which are quoted per euro. Currencies that are not supported are skipped.
+    pub fn parse_ecb_xml(text: &str) -> Result<Self, String> {
+        let mut rates = HashMap::new();
+        for element in text.split("<Cube").skip(1) {
+            let (Some(code), Some(rate)) = (attribute(element, "currency"), attribute(element, "rate"))
+            else {
+                continue;
+            };
+            if let (Some(currency), Ok(rate)) = (Currency::from_code(code), rate.parse::<f32>()) {
+                rates.insert(currency, rate);
+            }
+        }
+        Self::from_euro_rates(rates)
+    }
+
+    /// Parses rates per euro from a CSV file, either in the ECB layout (a `Date` header
+    /// row of currency codes followed by a row of rates) or as `currency,rate` rows.
+    pub fn parse_csv(text: &str) -> Result<Self, String> {
+        let rows: Vec<Vec<&str>> = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.split(',').map(str::trim).collect())
+            .collect();
+        let mut rates = HashMap::new();
+        let mut add = |code: &str, rate: &str| {
+            if let (Some(currency), Ok(rate)) = (Currency::from_code(code), rate.parse::<f32>()) {
+                rates.insert(currency, rate);
+            }
+        };
+        match rows.first() {
+            Some(header) if header.first().is_some_and(|field| field.eq_ignore_ascii_case("date")) => {
+                if let Some(values) = rows.get(1) {
+                    for (code, rate) in header.iter().zip(values).skip(1) {
+                        add(code, rate);
+                    }
+                }
+            }
+            _ => {
+                for row in &rows {
+                    if let [code, rate, ..] = row.as_slice() {
+                        add(code, rate);
+                    }
+                }
+            }
+        }
+        Self::from_euro_rates(rates)
+    }
+
+    /// Builds a table from rates per euro, failing when none were found.
+    fn from_euro_rates(mut rates: HashMap<Currency, f32>) -> Result<Self, String> {
+        rates.retain(|currency, rate| *currency != Currency::EUR && *rate > 0.0);
+        if rates.is_empty() {
+            return Err("No exchange rates for supported currencies found.".to_string());
+        }
+        Ok(Self {
+            base: Currency::EUR,
+            rates,
+        })
+    }
+}
+
+/// Returns the value of an XML attribute in single or double quotes.
+fn attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let start = element.find(&format!("{}=", name))? + name.len() + 1;
+    let quote = element[start..].chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let value = &element[start + 1..];
+    value.find(quote).map(|end| &value[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ecb_xml() {
+        let xml = "<gesmes:Envelope><Cube><Cube time='2024-12-10'>\
+            <Cube currency='USD' rate='1.0523'/><Cube currency=\"JPY\" rate=\"159.8\"/>\
+            <Cube currency='XAU' rate='0.0004'/></Cube></Cube></gesmes:Envelope>";
+        let rates = ExchangeRates::parse_ecb_xml(xml).unwrap();
+        assert_eq!(rates.base, Currency::EUR);
+        assert_eq!(rates.rates.len(), 2);
+        assert_eq!(rates.rate(Currency::USD), Some(1.0523));
+        assert_eq!(rates.rate(Currency::JPY), Some(159.8));
+    }
+
+    #[test]
+    fn parses_ecb_csv() {
+        let csv = "Date, USD, JPY, GBP,\n10 December 2024, 1.0523, 159.80, 0.8254,\n";
+        let rates = ExchangeRates::parse_csv(csv).unwrap();
+        assert_eq!(rates.rate(Currency::GBP), Some(0.8254));
+        assert_eq!(rates.rates.len(), 3);
+    }
+
+    #[test]
+    fn parses_csv_rows() {
+        let rates = ExchangeRates::parse_csv("usd,1.08\nCHF,0.95\nEUR,1.0\nXYZ,2.0\n").unwrap();
+        assert_eq!(rates.rate(Currency::USD), Some(1.08));
+        assert_eq!(rates.rate(Currency::CHF), Some(0.95));
+        assert_eq!(rates.rates.len(), 2);
+    }
+
+    #[test]
+    fn rejects_files_without_rates() {
+        assert!(ExchangeRates::parse_csv("hello\nworld").is_err());
+        assert!(ExchangeRates::parse_ecb_xml("<Cube currency='XAU' rate='1'/>").is_err());
+    }
+
+    #[test]
+    fn rebases_rates_on_a_new_currency() {
+        let mut rates = ExchangeRates::default();
+        assert!(rates.rebase(Currency::USD));
+        assert_eq!(rates.base, Currency::USD);
+        assert_eq!(rates.rate(Currency::USD), Some(1.0));
+        let euro = rates.rate(Currency::EUR).unwrap();
+        assert!((euro - 1.0 / 1.08).abs() < 1e-6);
+        let pound = rates.rate(Currency::GBP).unwrap();
+        assert!((pound - 0.85 / 1.08).abs() < 1e-6);
+        assert!(!rates.rates.contains_key(&Currency::USD));
+    }
+
+    #[test]
+    fn rebase_needs_a_rate_for_the_new_base() {
+        let mut rates = ExchangeRates::default();
+        assert!(!rates.rebase(Currency::from_code("KWD").unwrap()));
+        assert_eq!(rates.base, Currency::EUR);
+    }
+
+    #[test]
+    fn converts_across_the_base() {
+        let rates = ExchangeRates::default();
+        let yen = rates.convert(10.8, Currency::USD, Currency::JPY).unwrap();
+        assert!((yen - 1620.0).abs() < 0.01);
+        assert_eq!(rates.convert(1.0, Currency::USD, Currency::from_code("KWD").unwrap()), None);
+    }
+}
@@ -0,0 +1,91 @@
+// Default flush volume between two different filaments in mm³
+const DEFAULT_FLUSH_VOLUME: f32 = 280.0;
+
+/// Flushing volumes and tool changes between the filaments of a multi-material job.
+#[derive(Clone, Default)]
+pub struct FlushPlan {
+    pub enabled: bool,          // Whether purge is estimated from this plan
+    pub volumes: Vec<Vec<f32>>, // Flush volume in mm³ from filament [from] to filament [to]
+    pub changes: Vec<Vec<u32>>, // Number of tool changes from filament [from] to filament [to]
+}
+
+impl FlushPlan {
+    /// Resizes both matrices to `count` filaments, keeping existing entries.
+    pub fn resize(&mut self, count: usize) {
+        self.volumes.truncate(count);
+        self.changes.truncate(count);
+        for row in self.volumes.iter_mut() {
+            row.resize(count, DEFAULT_FLUSH_VOLUME);
+        }
+        for row in self.changes.iter_mut() {
+            row.resize(count, 0);
+        }
+        while self.volumes.len() < count {
+            self.volumes.push(vec![DEFAULT_FLUSH_VOLUME; count]);
+        }
+        while self.changes.len() < count {
+            self.changes.push(vec![0; count]);
+        }
+        for i in 0..count {
+            self.volumes[i][i] = 0.0;
+        }
+    }
+
+    /// Removes a filament's row and column from both matrices.
+    pub fn remove(&mut self, index: usize) {
+        if index < self.volumes.len() {
+            self.volumes.remove(index);
+            for row in self.volumes.iter_mut() {
+                row.remove(index);
+            }
+        }
+        if index < self.changes.len() {
+            self.changes.remove(index);
+            for row in self.changes.iter_mut() {
+                row.remove(index);
+            }
+        }
+    }
+
+    /// Replaces the tool change counts with those of a sequence of 1-based filament
+    /// numbers such as "1, 2, 1, 3". Returns `false` if the sequence is invalid.
+    pub fn set_changes_from_sequence(&mut self, sequence: &str) -> bool {
+        let count = self.changes.len();
+        let parsed: Result<Vec<usize>, _> = sequence
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.parse::<usize>())
+            .collect();
+        let Ok(steps) = parsed else {
+            return false;
+        };
+        if steps.iter().any(|&step| step == 0 || step > count) {
+            return false;
+        }
+
+        for row in self.changes.iter_mut() {
+            row.fill(0);
+        }
+        for pair in steps.windows(2) {
+            let (from, to) = (pair[0] - 1, pair[1] - 1);
+            if from != to {
+                self.changes[from][to] += 1;
+            }
+        }
+        true
+    }
+
+    /// Returns the total flushed volume in mm³ charged to each filament.
+    ///
+    /// A flush from A to B pushes A out with B, so the waste is charged to B.
+    pub fn flushed_volumes(&self) -> Vec<f32> {
+        let count = self.volumes.len();
+        (0..count)
+            .map(|to| {
+                (0..count)
+                    .map(|from| self.volumes[from][to] * self.changes[from][to] as f32)
+                    .sum()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,88 @@
+use crate::currency::Currency;
+use crate::money::Money;
+
+/// A kind of worker and what an hour of their time costs.
+pub struct LaborRole {
+    pub name: String,
+    pub hourly_rate: Money, // Cost of one attended hour
+}
+
+/// How often a labor phase is carried out within a job.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LaborScope {
+    Job,
+    Plate,
+    Part,
+}
+
+impl LaborScope {
+    pub const ALL: [LaborScope; 3] = [LaborScope::Job, LaborScope::Plate, LaborScope::Part];
+
+    /// Returns the display name of the scope.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LaborScope::Job => "per job",
+            LaborScope::Plate => "per plate",
+            LaborScope::Part => "per part",
+        }
+    }
+}
+
+/// A step of attended work such as slicing, plate removal or packing.
+#[derive(Clone)]
+pub struct LaborPhase {
+    pub name: String,
+    pub role: usize,       // Index of the labor role doing the work
+    pub minutes: f32,      // Attended minutes each time the phase is carried out
+    pub scope: LaborScope, // Whether the minutes apply per job, plate or part
+}
+
+impl LaborPhase {
+    /// Returns the attended minutes of the phase for a job with the given plates and parts.
+    pub fn total_minutes(&self, plates: u32, parts: u32) -> f32 {
+        let repetitions = match self.scope {
+            LaborScope::Job => 1,
+            LaborScope::Plate => plates,
+            LaborScope::Part => parts,
+        };
+        self.minutes * repetitions as f32
+    }
+}
+
+/// Returns the default labor roles.
+pub fn get_labor_roles() -> Vec<LaborRole> {
+    let eur = |amount| Money::from_f32(amount, Currency::EUR);
+    vec![
+        LaborRole {
+            name: "Operator".to_string(),
+            hourly_rate: eur(15.0),
+        },
+        LaborRole {
+            name: "Technician".to_string(),
+            hourly_rate: eur(25.0),
+        },
+        LaborRole {
+            name: "Packer".to_string(),
+            hourly_rate: eur(12.0),
+        },
+    ]
+}
+
+/// Returns the default labor phases of a job, without any time assigned yet.
+pub fn get_labor_phases() -> Vec<LaborPhase> {
+    [
+        ("Job prep and slicing", 0, LaborScope::Job),
+        ("Plate setup and removal", 0, LaborScope::Plate),
+        ("Support removal", 1, LaborScope::Part),
+        ("Quality check", 1, LaborScope::Part),
+        ("Packing", 2, LaborScope::Part),
+    ]
+    .into_iter()
+    .map(|(name, role, scope)| LaborPhase {
+        name: name.to_string(),
+        role,
+        minutes: 0.0,
+        scope,
+    })
+    .collect()
+}
@@ -1,165 +1,960 @@
 use std::collections::HashMap;
 
-pub enum Currency {
-    USD,
-    EUR,
-    GBP,
-}
+use crate::breakdown::{CostBreakdown, CostKind};
+use crate::currency::{Currency, NumberLocale};
+use crate::exchange::ExchangeRates;
+use crate::flushing::FlushPlan;
+use crate::labor::{LaborPhase, LaborRole};
+use crate::materials::{Additive, AdditiveSettings};
+use crate::money::Money;
+use crate::post_processing::{PostProcessOperation, PostProcessStep};
+use crate::pricing::{
+    DiscountTier, PaymentMethod, PipelineStage, PriceBreak, PricingMode, PricingStage,
+    ReverseQuote, RoundingPolicy, SalesChannel, TaxRate,
+};
+use crate::printer::{PrintConditions, PrinterProfile};
+use crate::tariff::{LoadSegment, Tariff};
+use crate::units::{FilamentQuantity, UnitSettings};
+
+// Print duration the failure rates of materials and printers refer to
+const FAILURE_REFERENCE_HOURS: f32 = 10.0;
+
+// Upper bound on the failure probability so the expected loss stays finite
+const MAX_FAILURE_PROBABILITY: f32 = 0.9;
 
+// Largest batch that can be quoted; the power draw is simulated plate by plate, so the
+// cap bounds the work of a calculation
+pub const MAX_QUANTITY: u32 = 10_000;
+
+// Standard filament diameters in mm
+pub const FILAMENT_DIAMETERS: [f32; 2] = [1.75, 2.85];
+
+#[derive(Clone)]
 pub struct FilamentUsage {
     pub brand: String,
     pub material: String,
-    pub weight: f32,         // Weight of the filament used (grams)
-    pub price_per_roll: f32, // Price of the filament roll
-    pub roll_weight: f32,    // Weight of the filament roll (grams)
-    pub is_carbon_based: bool, // Whether the filament is carbon-based
+    pub weight: f32,                   // Weight of the filament used per plate (grams)
+    pub price_per_roll: Money,         // Price of the filament roll, in the currency it was entered in
+    pub roll_weight: f32,              // Weight of the filament roll (grams)
+    pub additive: Additive,            // Filler blended into the filament (CF, GF, ...)
+    pub purge_weight: f32,             // Purge/waste assigned to this filament per plate (grams)
+    pub diameter: f32,                 // Filament diameter in mm (1.75 or 2.85)
+    pub density: f32,                  // Density in g/cm³, defaulting from the material
+    pub usage_input: FilamentQuantity, // Whether usage is entered as weight, length or volume
 }
 
-pub struct CalculatorLogic {
-    pub currency: Currency, // Selected currency
-    pub filament_prices: HashMap<&'static str, HashMap<&'static str, f32>>, // Filament prices
-    pub filaments: Vec<FilamentUsage>, // Multi-material usage data
-    pub purge_waste_weight: f32,       // Total purge/waste weight in grams
+impl FilamentUsage {
+    /// Sets the material and takes the density from its material entry.
+    pub fn set_material(&mut self, material: &str) {
+        self.material = material.to_string();
+        self.density = crate::materials::material_properties(material).density;
+    }
 
-    // Calculator fields
-    pub electricity_rate: f32, // Electricity rate in EUR/kWh
-    pub printer_wattage: f32, // Printer wattage in watts
-    pub print_time: f32, // Print time in hours
-    pub hourly_charge: f32, // User-defined charge per print hour
-    pub shipping_cost: f32, // Shipping cost in EUR
-    pub markup_percentage: f32, // Markup percentage for profit
-    pub wear_and_tear_cost: f32, // Wear and tear cost
-    pub total_cost: f32, // Total cost (calculated)
-    pub suggested_price: f32, // Suggested price (calculated)
-    pub suggested_price_with_post_processing: f32, // Suggested price with post-processing
-
-    // Post-processing fields
-    pub post_processing_hours: f32,
-    pub post_processing_rate: f32,
+    /// Returns the grams in one millimeter of this filament.
+    pub fn grams_per_mm(&self) -> f32 {
+        let radius = self.diameter / 2.0;
+        std::f32::consts::PI * radius * radius / 1000.0 * self.density // mm³ to cm³
+    }
+
+    /// Returns the filament length in millimeters that weighs the given grams.
+    pub fn length_mm(&self, grams: f32) -> f32 {
+        let grams_per_mm = self.grams_per_mm();
+        if grams_per_mm <= 0.0 {
+            return 0.0;
+        }
+        grams / grams_per_mm
+    }
+
+    /// Returns the volume in cm³ of the given grams of this filament.
+    pub fn volume_cm3(&self, grams: f32) -> f32 {
+        if self.density <= 0.0 {
+            return 0.0;
+        }
+        grams / self.density
+    }
+
+    /// Returns the price of the given grams of this filament, in the roll price's currency.
+    pub fn cost(&self, grams: f32) -> Money {
+        if self.roll_weight <= 0.0 {
+            return Money::zero(self.price_per_roll.currency());
+        }
+        self.price_per_roll.scale(grams / self.roll_weight)
+    }
 }
 
-impl Default for CalculatorLogic {
+impl Default for FilamentUsage {
     fn default() -> Self {
         Self {
-            currency: Currency::EUR,
-            filament_prices: crate::filament_prices::get_filament_prices(),
-            filaments: vec![FilamentUsage {
-                brand: "Custom".to_string(),
-                material: "Custom".to_string(),
-                weight: 0.0,
-                price_per_roll: 0.0,
-                roll_weight: 1000.0, // Default to 1kg
-                is_carbon_based: false,
-            }],
-            purge_waste_weight: 0.0,
+            brand: "Custom".to_string(),
+            material: "Custom".to_string(),
+            weight: 0.0,
+            price_per_roll: Money::zero(Currency::default()),
+            roll_weight: 1000.0, // Default to 1kg
+            additive: Additive::None,
+            purge_weight: 0.0,
+            diameter: FILAMENT_DIAMETERS[0],
+            density: crate::materials::material_properties("Custom").density,
+            usage_input: FilamentQuantity::Weight,
+        }
+    }
+}
 
-            electricity_rate: 0.26,
-            printer_wattage: 250.0,
-            print_time: 0.0,
-            hourly_charge: 2.50,
-            shipping_cost: 0.0,
-            markup_percentage: 20.0,
-            wear_and_tear_cost: 0.0,
-            total_cost: 0.0,
-            suggested_price: 0.0,
-            suggested_price_with_post_processing: 0.0,
+/// Description of a single print job, independent of shop-wide settings.
+#[derive(Clone)]
+pub struct PrintJob {
+    pub filaments: Vec<FilamentUsage>, // Multi-material usage data
+    pub printer: usize,                // Index of the printer profile used for the job
+    pub sales_channel: usize,          // Index of the sales channel the job is sold through
+    pub payment_method: usize,         // Index of the payment method the customer pays with
+    pub purge_waste_weight: f32,       // Unassigned purge/waste per plate in grams, split by usage
+    pub flush: FlushPlan,              // Flushing volumes between filaments (multi-color mode)
+    pub print_time: f32,               // Print time per plate in hours
+    pub start_time: f32,               // Planned start in hours since Monday 00:00
+    pub quantity: u32,                 // Number of parts ordered
+    pub parts_per_plate: u32,          // Number of parts printed on one plate
+    pub labor: Vec<LaborPhase>,        // Attended work phases such as slicing and packing
+    pub shipping_cost: Money,          // Shipping cost in the base currency
+    pub post_processing: Vec<PostProcessStep>, // Finishing operations from the catalog
+    pub failure_rate_override: Option<f32>, // Failure rate in percent per 10 hours, if not defaulted
+}
 
-            post_processing_hours: 0.0,
-            post_processing_rate: 15.0, // Default post-processing hourly rate
+impl Default for PrintJob {
+    fn default() -> Self {
+        let mut flush = FlushPlan::default();
+        flush.resize(1);
+        Self {
+            filaments: vec![FilamentUsage::default()],
+            printer: 0,
+            sales_channel: 0,
+            payment_method: 0,
+            purge_waste_weight: 0.0,
+            flush,
+            print_time: 0.0,
+            start_time: 8.0, // Monday 08:00
+            quantity: 1,
+            parts_per_plate: 1,
+            labor: crate::labor::get_labor_phases(),
+            shipping_cost: Money::zero(Currency::default()),
+            post_processing: Vec::new(),
+            failure_rate_override: None,
         }
     }
 }
 
-impl CalculatorLogic {
+impl PrintJob {
     /// Updates the number of filaments in use.
     pub fn update_filament_count(&mut self, count: usize) {
         if count > self.filaments.len() {
-            self.filaments.extend((self.filaments.len()..count).map(|_| FilamentUsage {
-                brand: "Custom".to_string(),
-                material: "Custom".to_string(),
-                weight: 0.0,
-                price_per_roll: 0.0,
-                roll_weight: 1000.0,
-                is_carbon_based: false,
-            }));
+            self.filaments
+                .extend((self.filaments.len()..count).map(|_| FilamentUsage::default()));
         } else {
             self.filaments.truncate(count);
         }
+        self.flush.resize(self.filaments.len());
     }
 
     /// Adds a new filament to the list (for multi-color/material mode).
     pub fn add_filament(&mut self) {
         if self.filaments.len() < 16 {
-            self.filaments.push(FilamentUsage {
-                brand: "Custom".to_string(),
-                material: "Custom".to_string(),
-                weight: 0.0,
-                price_per_roll: 0.0,
-                roll_weight: 1000.0,
-                is_carbon_based: false,
-            });
+            self.filaments.push(FilamentUsage::default());
+            self.flush.resize(self.filaments.len());
         }
     }
 
+    /// Returns the number of plates needed, counting a partly filled plate by its share of parts.
+    pub fn plates(&self) -> f32 {
+        self.quantity as f32 / self.parts_per_plate.max(1) as f32
+    }
+
+    /// Returns the number of plates that have to be set up, including a partly filled one.
+    pub fn plate_count(&self) -> u32 {
+        self.quantity.div_ceil(self.parts_per_plate.max(1))
+    }
+
+    /// Returns the machine time of the whole job in hours.
+    pub fn total_print_time(&self) -> f32 {
+        self.print_time * self.plates()
+    }
+
+    /// Returns the time spent on the job's labor phases in hours.
+    pub fn labor_hours(&self) -> f32 {
+        let (plates, parts) = (self.plate_count(), self.quantity);
+        let minutes: f32 = self
+            .labor
+            .iter()
+            .map(|phase| phase.total_minutes(plates, parts))
+            .sum();
+        minutes / 60.0
+    }
+
+    /// Returns the purge weight in grams each filament receives from the flush plan.
+    pub fn flush_purge_weights(&self) -> Vec<f32> {
+        let volumes = self.flush.flushed_volumes();
+        self.filaments
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                volumes.get(i).copied().unwrap_or(0.0) / 1000.0 * f.density // mm³ to cm³
+            })
+            .collect()
+    }
+
+    /// Returns the purge weight of each filament, including its share of the unassigned purge
+    /// and, when enabled, the purge estimated from the flush plan.
+    pub fn purge_weights(&self) -> Vec<f32> {
+        let total: f32 = self.filaments.iter().map(|f| f.weight).sum();
+        let count = self.filaments.len() as f32;
+        let flushed = if self.flush.enabled {
+            self.flush_purge_weights()
+        } else {
+            vec![0.0; self.filaments.len()]
+        };
+        self.filaments
+            .iter()
+            .zip(flushed)
+            .map(|(f, flushed)| {
+                let share = if total > 0.0 { f.weight / total } else { 1.0 / count };
+                f.purge_weight + self.purge_waste_weight * share + flushed
+            })
+            .collect()
+    }
+
     /// Removes a filament from the list by index.
     pub fn remove_filament(&mut self, index: usize) {
         if index < self.filaments.len() {
             self.filaments.remove(index);
+            self.flush.remove(index);
         }
     }
+}
 
-    /// Calculates the costs of the print job.
-    pub fn calculate_costs(&mut self) {
-        let mut total_filament_cost = 0.0;
+pub struct CalculatorLogic {
+    pub currency: Currency, // Currency results are shown in
+    pub locale: NumberLocale, // Number and currency formatting
+    pub units: UnitSettings, // Units that weights and lengths are entered in
+    pub exchange_rates: ExchangeRates, // Base currency of stored values and rates to convert them
+    pub filament_prices: HashMap<&'static str, HashMap<&'static str, Money>>, // Filament prices per 1 kg roll
+    pub printers: Vec<PrinterProfile>, // Available printer profiles
+    pub job: PrintJob,      // Job currently being quoted
+    pub breakdown: CostBreakdown, // Result of the last calculation
+    pub price_breaks: Vec<PriceBreak>, // Price-break table of the last calculation
+    pub target_price: Money, // Selling price to analyse in reverse, in the display currency, zero to skip
+    pub reverse_quote: Option<ReverseQuote>, // Analysis of the target price from the last calculation
+    pub cheapest_start: Option<(f32, Money)>, // Cheapest start time and electricity cost within 24 hours
+
+    // Calculator fields
+    pub tariff: Tariff, // Electricity tariff
+    pub hourly_charge: Money, // User-defined charge per print hour
+    pub labor_roles: Vec<LaborRole>, // Workers and hourly rates for attended labor phases
+    pub pricing_mode: PricingMode, // Whether profit is set as markup or margin
+    pub pricing_pipeline: Vec<PipelineStage>, // Cost stages and whether markup applies to them
+    pub markup_percentage: f32, // Markup percentage for profit
+    pub margin_percentage: f32, // Gross margin percentage for profit
+    pub additive_settings: HashMap<Additive, AdditiveSettings>, // Surcharge and wear per additive
+    pub discount_tiers: Vec<DiscountTier>, // Quantity discounts on the suggested price
+    pub tax_rates: Vec<TaxRate>, // Named tax rates, applied when enabled
+    pub sales_channels: Vec<SalesChannel>, // Marketplace and webshop fee profiles
+    pub payment_methods: Vec<PaymentMethod>, // Card, PayPal and other processor fee profiles
+    pub prices_include_tax: bool, // Quote tax-inclusive (consumer) instead of tax-exclusive (B2B)
+    pub rounding: RoundingPolicy, // Rounding applied to the quoted price
+    pub setup_fee: Money, // Fixed fee per job for slicing, loading and removal
+    pub minimum_order_value: Money, // Smallest price accepted for a job, before fees and tax
+    pub post_processing_operations: Vec<PostProcessOperation>, // Catalog of finishing operations
+}
+
+impl Default for CalculatorLogic {
+    fn default() -> Self {
+        Self {
+            currency: Currency::EUR,
+            locale: NumberLocale::default(),
+            units: UnitSettings::default(),
+            exchange_rates: ExchangeRates::default(),
+            filament_prices: crate::filament_prices::get_filament_prices(),
+            printers: crate::printer::get_printer_profiles(),
+            job: PrintJob::default(),
+            breakdown: CostBreakdown::default(),
+            price_breaks: Vec::new(),
+            target_price: Money::zero(Currency::EUR),
+            reverse_quote: None,
+            cheapest_start: None,
+
+            tariff: Tariff::default(),
+            hourly_charge: Money::from_f32(2.50, Currency::EUR),
+            labor_roles: crate::labor::get_labor_roles(),
+            pricing_mode: PricingMode::Markup,
+            pricing_pipeline: crate::pricing::get_pricing_pipeline(),
+            markup_percentage: 20.0,
+            margin_percentage: 20.0,
+            additive_settings: crate::materials::get_additive_settings(),
+            discount_tiers: Vec::new(), // Quotes are not discounted until tiers are added
+            tax_rates: crate::pricing::get_tax_rates(),
+            sales_channels: crate::pricing::get_sales_channels(),
+            payment_methods: crate::pricing::get_payment_methods(),
+            prices_include_tax: false,
+            rounding: RoundingPolicy::default(),
+            setup_fee: Money::zero(Currency::EUR),
+            minimum_order_value: Money::zero(Currency::EUR),
+            post_processing_operations: crate::post_processing::get_post_processing_operations(),
+        }
+    }
+}
+
+impl CalculatorLogic {
+    /// Returns the printer profile a job runs on, falling back to the first profile.
+    pub fn printer_for(&self, job: &PrintJob) -> Option<&PrinterProfile> {
+        self.printers.get(job.printer).or_else(|| self.printers.first())
+    }
+
+    /// Returns the sales channel a job is sold through, falling back to the first channel.
+    pub fn sales_channel_for(&self, job: &PrintJob) -> Option<&SalesChannel> {
+        self.sales_channels
+            .get(job.sales_channel)
+            .or_else(|| self.sales_channels.first())
+    }
+
+    /// Returns the labor role at an index, falling back to the first role.
+    pub fn labor_role_for(&self, role: usize) -> Option<&LaborRole> {
+        self.labor_roles.get(role).or_else(|| self.labor_roles.first())
+    }
+
+    /// Returns the attended time of a job in hours, from its labor phases and
+    /// post-processing operations.
+    pub fn attended_hours(&self, job: &PrintJob) -> f32 {
+        let post_processing_minutes: f32 = job
+            .post_processing
+            .iter()
+            .filter_map(|step| {
+                self.post_processing_operations
+                    .get(step.operation)
+                    .map(|operation| operation.minutes_per_part * step.repetitions(job.quantity))
+            })
+            .sum();
+        job.labor_hours() + post_processing_minutes / 60.0
+    }
+
+    /// Returns the payment method of a job, falling back to the first method.
+    pub fn payment_method_for(&self, job: &PrintJob) -> Option<&PaymentMethod> {
+        self.payment_methods
+            .get(job.payment_method)
+            .or_else(|| self.payment_methods.first())
+    }
+
+    /// Returns the combined rate (0.0 to 1.0) of all enabled taxes.
+    pub fn total_tax_rate(&self) -> f32 {
+        self.tax_rates
+            .iter()
+            .filter(|tax| tax.enabled)
+            .map(|tax| tax.rate_percentage / 100.0)
+            .sum()
+    }
+
+    /// Converts an amount to another currency with the exchange-rate table, keeping the
+    /// number unchanged when a rate is unknown.
+    pub fn convert(&self, amount: Money, to: Currency) -> Money {
+        let rate = self.exchange_rates.convert(1.0, amount.currency(), to).unwrap_or(1.0);
+        amount.convert(to, rate)
+    }
+
+    /// Converts an amount to the display currency.
+    pub fn to_display(&self, amount: Money) -> Money {
+        self.convert(amount, self.currency)
+    }
+
+    /// Returns whether the markup is charged on a pricing stage.
+    pub fn markup_applies(&self, stage: PricingStage) -> bool {
+        self.pricing_pipeline
+            .iter()
+            .any(|entry| entry.stage == stage && entry.markup_applies)
+    }
+
+    /// Returns the hottest nozzle and bed temperatures among the job's materials, and
+    /// whether any of them needs a heated chamber.
+    pub fn print_conditions(&self, job: &PrintJob) -> PrintConditions {
+        let mut conditions = PrintConditions {
+            nozzle_temp: 0.0,
+            bed_temp: 0.0,
+            heated_chamber: false,
+        };
+        for filament in &job.filaments {
+            let properties = crate::materials::material_properties(&filament.material);
+            conditions.nozzle_temp = conditions.nozzle_temp.max(properties.nozzle_temp);
+            conditions.bed_temp = conditions.bed_temp.max(properties.bed_temp);
+            conditions.heated_chamber |= properties.needs_chamber;
+        }
+        conditions
+    }
+
+    /// Returns the printer's power draw over the whole job.
+    pub fn power_load(&self, job: &PrintJob, printer: &PrinterProfile) -> Vec<LoadSegment> {
+        printer
+            .power
+            .load(&self.print_conditions(job), job.plates(), job.print_time)
+    }
+
+    /// Returns the additive surcharge for the given grams of a filament, in the base currency.
+    fn additive_surcharge(&self, filament: &FilamentUsage, grams: f32) -> Money {
+        let surcharge = self
+            .additive_settings
+            .get(&filament.additive)
+            .map_or(0.0, |settings| settings.surcharge_per_kg * (grams / 1000.0));
+        Money::from_f32(surcharge, self.exchange_rates.base)
+    }
+
+    /// Returns the wear factor for abrasion-sensitive parts, weighted by filament usage.
+    pub fn nozzle_wear_factor(&self, job: &PrintJob) -> f32 {
+        let total: f32 = job.filaments.iter().map(|f| f.weight).sum();
+        if total <= 0.0 {
+            return 1.0;
+        }
+        job.filaments
+            .iter()
+            .map(|f| {
+                let factor = self
+                    .additive_settings
+                    .get(&f.additive)
+                    .map_or(1.0, |settings| settings.nozzle_wear_factor);
+                factor * f.weight / total
+            })
+            .sum()
+    }
+
+    /// Returns the failure rate in percent per 10 print hours, from the override or
+    /// from the riskiest material combined with the printer.
+    pub fn base_failure_rate(&self, job: &PrintJob) -> f32 {
+        if let Some(rate) = job.failure_rate_override {
+            return rate;
+        }
+        let material_rate = job
+            .filaments
+            .iter()
+            .filter(|f| f.weight > 0.0)
+            .map(|f| crate::materials::material_properties(&f.material).failure_rate)
+            .fold(0.0, f32::max);
+        let printer_rate = self.printer_for(job).map_or(0.0, |printer| printer.failure_rate);
+        100.0 * (1.0 - (1.0 - material_rate / 100.0) * (1.0 - printer_rate / 100.0))
+    }
+
+    /// Returns the probability (0.0 to 1.0) that a plate fails, growing with print time.
+    pub fn failure_probability(&self, job: &PrintJob) -> f32 {
+        let rate = (self.base_failure_rate(job) / 100.0).clamp(0.0, MAX_FAILURE_PROBABILITY);
+        let probability = 1.0 - (1.0 - rate).powf(job.print_time.max(0.0) / FAILURE_REFERENCE_HOURS);
+        probability.min(MAX_FAILURE_PROBABILITY)
+    }
+
+    /// Calculates the itemised costs and suggested prices for a job, following the stages
+    /// of the pricing pipeline in order.
+    pub fn calculate(&self, job: &PrintJob) -> CostBreakdown {
+        let mut breakdown = CostBreakdown::new(self.currency, job.quantity);
+        breakdown.machine_hours = job.total_print_time();
+        breakdown.attended_hours = self.attended_hours(job);
+        let plates = job.plates();
+        let print_time = job.total_print_time();
 
         // Calculate costs for each filament
-        for filament in &self.filaments {
-            let cost_per_gram = filament.price_per_roll / filament.roll_weight;
-            total_filament_cost += cost_per_gram * filament.weight;
+        for (i, filament) in job.filaments.iter().enumerate() {
+            let weight = filament.weight * plates;
+            breakdown.push(
+                CostKind::Filament,
+                format!("Filament #{} ({} {})", i + 1, filament.brand, filament.material),
+                self.to_display(filament.cost(weight)),
+            );
+
+            if filament.additive != Additive::None {
+                breakdown.push(
+                    CostKind::Filament,
+                    format!("Filament #{} {} surcharge", i + 1, filament.additive.label()),
+                    self.to_display(self.additive_surcharge(filament, weight)),
+                );
+            }
+        }
+
+        // Price purge/waste with the filament being flushed
+        let weight_unit = self.units.filament_weight;
+        for (i, (filament, purge)) in job.filaments.iter().zip(job.purge_weights()).enumerate() {
+            let purge = purge * plates;
+            if purge <= 0.0 {
+                continue;
+            }
+            breakdown.push(
+                CostKind::Purge,
+                format!(
+                    "Filament #{} purge ({:.*} {})",
+                    i + 1,
+                    weight_unit.decimals(),
+                    weight_unit.convert(purge),
+                    weight_unit.label()
+                ),
+                self.to_display(filament.cost(purge))
+                    + self.to_display(self.additive_surcharge(filament, purge)),
+            );
+        }
+
+        if let Some(printer) = self.printer_for(job) {
+            // Calculate electricity costs from the power profile across the tariff's time bands
+            let (kwh, electricity_cost) =
+                self.tariff.energy_cost(job.start_time, &self.power_load(job, printer));
+            breakdown.push(
+                CostKind::Electricity,
+                format!("Electricity ({:.2} kWh)", kwh),
+                self.to_display(Money::from_f32(electricity_cost, self.exchange_rates.base)),
+            );
+
+            // Calculate wear and tear costs from depreciation and maintenance
+            breakdown.push(
+                CostKind::Wear,
+                format!("Wear and tear ({})", printer.name),
+                self.to_display(printer.wear_cost(print_time)),
+            );
+
+            // Amortise consumable parts, wearing faster with abrasive additives
+            let wear_factor = self.nozzle_wear_factor(job);
+            for part in &printer.consumables {
+                breakdown.push(
+                    CostKind::Consumables,
+                    format!("{} (consumable)", part.name),
+                    self.to_display(part.cost(print_time, wear_factor)),
+                );
+            }
+        }
+
+        breakdown.push(
+            CostKind::MachineTime,
+            "Machine time",
+            self.to_display(self.hourly_charge.scale(print_time)),
+        );
+
+        // Expected cost of failed attempts before a successful print
+        let probability = self.failure_probability(job);
+        if probability > 0.0 {
+            let at_risk = breakdown.total_where(|line| {
+                matches!(
+                    line.kind,
+                    CostKind::Filament
+                        | CostKind::Purge
+                        | CostKind::Electricity
+                        | CostKind::Wear
+                        | CostKind::Consumables
+                        | CostKind::MachineTime
+                )
+            });
+            breakdown.push(
+                CostKind::FailureRisk,
+                format!("Failure allowance ({:.1}% risk)", probability * 100.0),
+                at_risk.scale(probability / (1.0 - probability)),
+            );
+        }
+
+        // Attended labor, one line per phase at the rate of its role
+        let plate_count = job.plate_count();
+        for phase in &job.labor {
+            let minutes = phase.total_minutes(plate_count, job.quantity);
+            if minutes <= 0.0 {
+                continue;
+            }
+            let Some(role) = self.labor_role_for(phase.role) else {
+                continue;
+            };
+            breakdown.push(
+                CostKind::Labor,
+                format!("{} ({:.0} min, {})", phase.name, minutes, role.name),
+                self.to_display(role.hourly_rate.scale(minutes / 60.0)),
+            );
+        }
+
+        breakdown.push(CostKind::Shipping, "Shipping", self.to_display(job.shipping_cost));
+
+        // Post-processing operations, with attended time and consumables per part
+        for step in &job.post_processing {
+            let Some(operation) = self.post_processing_operations.get(step.operation) else {
+                continue;
+            };
+            let repetitions = step.repetitions(job.quantity);
+            let minutes = operation.minutes_per_part * repetitions;
+            if let Some(role) = self.labor_role_for(operation.role) {
+                if minutes > 0.0 {
+                    breakdown.push(
+                        CostKind::PostProcessing,
+                        format!("{} ({:.0} min, {})", operation.name, minutes, role.name),
+                        self.to_display(role.hourly_rate.scale(minutes / 60.0)),
+                    );
+                }
+            }
+            if !operation.consumable_cost_per_part.is_zero() {
+                breakdown.push(
+                    CostKind::PostProcessing,
+                    format!("{} ({})", operation.name, operation.consumables),
+                    self.to_display(operation.consumable_cost_per_part.scale(repetitions)),
+                );
+            }
+        }
 
-            if filament.is_carbon_based {
-                total_filament_cost += 10.0 * (filament.weight / 1000.0); // Extra cost for CF/GF
+        // Calculate total costs, and the part of them the markup applies to
+        breakdown.total_cost = breakdown.total_where(|_| true);
+        breakdown.markup_base = breakdown.total_where(|line| self.markup_applies(line.kind.stage()));
+
+        // Calculate suggested prices from markup on cost or margin on price
+        let base = breakdown.markup_base;
+        let (label, markup) = match self.pricing_mode {
+            PricingMode::Markup => (
+                format!("Markup ({}%)", self.markup_percentage),
+                base.scale(self.markup_percentage / 100.0),
+            ),
+            PricingMode::Margin => {
+                let margin = (self.margin_percentage / 100.0).min(0.99);
+                (
+                    format!("Margin ({}%)", self.margin_percentage),
+                    base.scale(1.0 / (1.0 - margin)) - base,
+                )
             }
+        };
+        breakdown.push(CostKind::Markup, label, markup);
+        breakdown.suggested_price = breakdown.total_cost + markup;
+
+        // Apply the quantity discount tier the job reaches
+        let discount_percentage = crate::pricing::discount_for(&self.discount_tiers, job.quantity);
+        if discount_percentage > 0.0 {
+            let discount = breakdown.suggested_price.scale(discount_percentage / 100.0);
+            breakdown.push(
+                CostKind::Discount,
+                format!("Quantity discount (-{}%)", discount_percentage),
+                -discount,
+            );
+            breakdown.suggested_price -= discount;
         }
 
-        // Add purge/waste material cost
-        total_filament_cost += (self.purge_waste_weight / 1000.0) * 10.0; // Example purge material cost
+        // Charge the per-job setup fee on top of the marked-up price
+        let setup_fee = self.to_display(self.setup_fee);
+        if setup_fee > Money::zero(self.currency) {
+            breakdown.push(CostKind::SetupFee, "Setup fee", setup_fee);
+            breakdown.suggested_price += setup_fee;
+        }
 
-        // Calculate electricity costs
-        let electricity_cost =
-            (self.printer_wattage / 1000.0) * self.print_time * self.electricity_rate;
+        // Raise small orders to the minimum order value (before fees and tax)
+        let minimum_order_value = self.to_display(self.minimum_order_value);
+        if breakdown.suggested_price < minimum_order_value {
+            breakdown.minimum_order_adjustment = minimum_order_value - breakdown.suggested_price;
+            breakdown.push(
+                CostKind::MinimumOrder,
+                "Minimum order adjustment",
+                breakdown.minimum_order_adjustment,
+            );
+            breakdown.suggested_price = minimum_order_value;
+        }
 
-        // Calculate wear and tear costs
-        self.wear_and_tear_cost = self.print_time * 0.05; // Simplified wear and tear (EUR/hour)
-        self.wear_and_tear_cost += self.purge_waste_weight * 0.01; // Extra wear for purge waste
+        let enabled_taxes: Vec<&TaxRate> = self.tax_rates.iter().filter(|tax| tax.enabled).collect();
+        let total_rate = self.total_tax_rate();
 
-        // Calculate post-processing costs
-        let post_processing_cost = self.post_processing_hours * self.post_processing_rate;
+        // Solve for the list price that still leaves the marked-up price after channel and
+        // payment fees, in the base currency the fixed fees are entered in
+        let channel = self.sales_channel_for(job);
+        let payment = self.payment_method_for(job);
+        let list_price = crate::pricing::solve_list_price(
+            self.convert(breakdown.suggested_price, self.exchange_rates.base),
+            job.shipping_cost,
+            total_rate,
+            channel,
+            payment,
+        );
+        if let Some(channel) = channel {
+            let fees = self.to_display(channel.fees(list_price, job.shipping_cost));
+            if !fees.is_zero() {
+                breakdown.push(CostKind::Fees, format!("{} fees", channel.name), fees);
+                breakdown.suggested_price += fees;
+            }
+        }
+        if let Some(payment) = payment {
+            let fee = self.to_display(payment.fee(list_price.scale(1.0 + total_rate)));
+            if !fee.is_zero() {
+                breakdown.push(CostKind::Fees, format!("Payment fee ({})", payment.name), fee);
+                breakdown.suggested_price += fee;
+            }
+        }
 
-        // Calculate total costs
-        self.total_cost = total_filament_cost + electricity_cost + self.wear_and_tear_cost
-            + self.hourly_charge * self.print_time + self.shipping_cost + post_processing_cost;
+        // Tax on top of a net price, or backed out of a gross price so that net plus tax
+        // always equals the quoted amount
+        let tax_lines = |price: Money, backed_out: bool| -> Vec<(String, Money)> {
+            enabled_taxes
+                .iter()
+                .map(|tax| {
+                    let rate = tax.rate_percentage / 100.0;
+                    let amount = if backed_out {
+                        price.scale(rate / (1.0 + total_rate))
+                    } else {
+                        price.scale(rate)
+                    };
+                    (format!("{} ({}%)", tax.name, tax.rate_percentage), amount)
+                })
+                .collect()
+        };
+        let sum = |lines: &[(String, Money)]| {
+            lines
+                .iter()
+                .fold(Money::zero(self.currency), |total, (_, amount)| total + *amount)
+        };
 
-        // Calculate suggested prices
-        self.suggested_price = self.total_cost * (1.0 + self.markup_percentage / 100.0);
-        self.suggested_price_with_post_processing = self.suggested_price + post_processing_cost;
+        // Round the quoted price: the net price for tax-exclusive quotes, the gross price
+        // for tax-inclusive quotes
+        breakdown.tax_inclusive = self.prices_include_tax;
+        let net_price = breakdown.suggested_price;
+        breakdown.unrounded_price = if self.prices_include_tax {
+            net_price + sum(&tax_lines(net_price, false))
+        } else {
+            net_price
+        };
+        let rounded_price = self.rounding.apply(breakdown.unrounded_price);
+        let taxes = if rounded_price == breakdown.unrounded_price {
+            tax_lines(net_price, false)
+        } else {
+            let taxes = tax_lines(rounded_price, self.prices_include_tax);
+            let rounded_net = if self.prices_include_tax {
+                rounded_price - sum(&taxes)
+            } else {
+                rounded_price
+            };
+            breakdown.push(CostKind::Rounding, "Rounding adjustment", rounded_net - net_price);
+            breakdown.suggested_price = rounded_net;
+            taxes
+        };
+        breakdown.tax = sum(&taxes);
+        for (label, amount) in taxes {
+            breakdown.push(CostKind::Tax, label, amount);
+        }
+        breakdown.gross_price = breakdown.suggested_price + breakdown.tax;
+
+        breakdown
     }
 
-    /// Switches the currency symbol.
-    pub fn switch_currency(&mut self) {
-        self.currency = match self.currency {
-            Currency::USD => Currency::EUR,
-            Currency::EUR => Currency::GBP,
-            Currency::GBP => Currency::USD,
+    /// Works out the markup, margin and profit per machine hour a job earns when sold at
+    /// `target_price`, given as quoted (including tax in tax-inclusive mode).
+    pub fn reverse_quote(&self, job: &PrintJob, target_price: Money) -> ReverseQuote {
+        let tax_rate = self.total_tax_rate();
+        let target_price = self.to_display(target_price);
+        let list_price = if self.prices_include_tax {
+            target_price.scale(1.0 / (1.0 + tax_rate))
+        } else {
+            target_price
         };
+
+        // Fees are worked out in the base currency the fixed fees are entered in
+        let base_list_price = self.convert(list_price, self.exchange_rates.base);
+        let mut fees = Money::zero(self.currency);
+        if let Some(channel) = self.sales_channel_for(job) {
+            fees += self.to_display(channel.fees(base_list_price, job.shipping_cost));
+        }
+        if let Some(payment) = self.payment_method_for(job) {
+            fees += self.to_display(payment.fee(base_list_price.scale(1.0 + tax_rate)));
+        }
+
+        let total_cost = self.calculate(job).total_cost;
+        let net_revenue = list_price - fees;
+        let profit = net_revenue - total_cost;
+        let machine_hours = job.total_print_time();
+        ReverseQuote {
+            target_price,
+            net_revenue,
+            profit,
+            markup_percentage: crate::pricing::percentage_of(profit, total_cost),
+            margin_percentage: crate::pricing::percentage_of(profit, net_revenue),
+            profit_per_machine_hour: if machine_hours > 0.0 {
+                profit.scale(1.0 / machine_hours)
+            } else {
+                Money::zero(self.currency)
+            },
+        }
+    }
+
+    /// Calculates the unit cost, unit price and margin of a job at each quantity.
+    pub fn price_breaks(&self, job: &PrintJob, quantities: &[u32]) -> Vec<PriceBreak> {
+        let mut job = job.clone();
+        quantities
+            .iter()
+            .map(|&quantity| {
+                job.quantity = quantity;
+                PriceBreak::from_breakdown(&self.calculate(&job))
+            })
+            .collect()
+    }
+
+    /// Calculates the costs of the current print job.
+    pub fn calculate_costs(&mut self) {
+        self.breakdown = self.calculate(&self.job);
+        self.price_breaks = self.price_breaks(&self.job, &crate::pricing::PRICE_BREAK_QUANTITIES);
+        self.reverse_quote = (self.target_price > Money::zero(self.target_price.currency()))
+            .then(|| self.reverse_quote(&self.job, self.target_price));
+        self.cheapest_start = self.printer_for(&self.job).map(|printer| {
+            let (start, cost) = self
+                .tariff
+                .cheapest_start(self.job.start_time, &self.power_load(&self.job, printer));
+            (start, self.to_display(Money::from_f32(cost, self.exchange_rates.base)))
+        });
     }
 
-    /// Returns the currency symbol as a string.
-    pub fn currency_symbol(&self) -> &str {
-        match self.currency {
-            Currency::USD => "$",
-            Currency::EUR => "€",
-            Currency::GBP => "£",
+    /// Changes the base currency, converting every stored price, fee and per-kWh or per-kg
+    /// rate at the current exchange rate. Filament roll prices keep the currency they were
+    /// entered in. Returns false and changes nothing when the new base has no rate.
+    pub fn set_base_currency(&mut self, base: Currency) -> bool {
+        let Some(rate) = self.exchange_rates.rate(base) else {
+            return false;
+        };
+        let rates = &self.exchange_rates;
+        let convert = |amount: &mut Money| {
+            let rate = rates.convert(1.0, amount.currency(), base).unwrap_or(1.0);
+            *amount = amount.convert(base, rate);
+        };
+        for amount in [
+            &mut self.hourly_charge,
+            &mut self.setup_fee,
+            &mut self.minimum_order_value,
+            &mut self.job.shipping_cost,
+        ] {
+            convert(amount);
+        }
+        for role in &mut self.labor_roles {
+            convert(&mut role.hourly_rate);
+        }
+        for printer in &mut self.printers {
+            convert(&mut printer.purchase_price);
+            convert(&mut printer.residual_value);
+            convert(&mut printer.yearly_maintenance);
+            for part in &mut printer.consumables {
+                convert(&mut part.cost);
+            }
+        }
+        for operation in &mut self.post_processing_operations {
+            convert(&mut operation.consumable_cost_per_part);
+        }
+        for channel in &mut self.sales_channels {
+            convert(&mut channel.fixed_fee);
+        }
+        for payment in &mut self.payment_methods {
+            convert(&mut payment.fixed_fee);
+        }
+
+        // Rates per kWh and per kg are finer than the minor unit and stay plain numbers
+        self.tariff.base_rate *= rate;
+        for band in &mut self.tariff.bands {
+            band.rate *= rate;
+        }
+        for tier in &mut self.tariff.tiers {
+            tier.rate *= rate;
         }
+        for settings in self.additive_settings.values_mut() {
+            settings.surcharge_per_kg *= rate;
+        }
+
+        self.exchange_rates.rebase(base);
+        if !self.breakdown.lines.is_empty() {
+            self.calculate_costs();
+        }
+        true
+    }
+
+    /// Sets the display currency, converting the target price and the results of the last
+    /// calculation.
+    pub fn set_currency(&mut self, currency: Currency) {
+        self.target_price = self.convert(self.target_price, currency);
+        self.currency = currency;
+        if !self.breakdown.lines.is_empty() {
+            self.calculate_costs();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changing_the_base_currency_keeps_quotes() {
+        let mut logic = CalculatorLogic::default();
+        logic.job.filaments[0].weight = 120.0;
+        logic.job.filaments[0].price_per_roll = Money::from_f64(25.0, Currency::EUR);
+        logic.job.print_time = 4.0;
+        logic.job.labor[0].minutes = 15.0;
+        logic.job.shipping_cost = Money::from_f64(4.90, Currency::EUR);
+        logic.job.sales_channel = 1;
+        logic.job.payment_method = 1;
+        logic.calculate_costs();
+        let before = logic.breakdown.suggested_price;
+
+        assert!(logic.set_base_currency(Currency::USD));
+        assert_eq!(logic.hourly_charge, Money::from_f64(2.70, Currency::USD));
+        assert_eq!(logic.job.shipping_cost.currency(), Currency::USD);
+        assert_eq!(logic.job.filaments[0].price_per_roll.currency(), Currency::EUR);
+        let drift = (logic.breakdown.suggested_price - before).minor_units();
+        assert!(drift.abs() <= 2, "quote moved by {} cents", drift);
+    }
+
+    fn post_processed_job() -> CalculatorLogic {
+        let mut logic = CalculatorLogic::default();
+        logic.job.filaments[0].weight = 50.0;
+        logic.job.filaments[0].price_per_roll = Money::from_f64(20.0, Currency::EUR);
+        logic.job.print_time = 2.0;
+        logic.job.quantity = 3;
+        logic.job.post_processing.push(PostProcessStep { operation: 0, repeats: 1 });
+        logic
+    }
+
+    #[test]
+    fn post_processing_is_charged_once() {
+        let logic = post_processed_job();
+        let breakdown = logic.calculate(&logic.job);
+        let post_processing = breakdown.total_for_stage(PricingStage::PostProcessing);
+        assert!(post_processing > Money::zero(Currency::EUR));
+        let cost_stages = PricingStage::ALL
+            .into_iter()
+            .filter(PricingStage::is_cost)
+            .fold(Money::zero(Currency::EUR), |total, stage| total + breakdown.total_for_stage(stage));
+        assert_eq!(breakdown.total_cost, cost_stages);
+        assert_eq!(breakdown.markup_base, breakdown.total_cost);
+        assert_eq!(
+            breakdown.suggested_price,
+            breakdown.total_cost + breakdown.total_for(CostKind::Markup)
+        );
+    }
+
+    #[test]
+    fn unticked_stages_are_not_marked_up() {
+        let mut logic = post_processed_job();
+        for entry in &mut logic.pricing_pipeline {
+            entry.markup_applies = entry.stage != PricingStage::PostProcessing;
+        }
+        let breakdown = logic.calculate(&logic.job);
+        let post_processing = breakdown.total_for_stage(PricingStage::PostProcessing);
+        assert_eq!(breakdown.markup_base, breakdown.total_cost - post_processing);
+        assert_eq!(
+            breakdown.total_for(CostKind::Markup),
+            breakdown.markup_base.scale(logic.markup_percentage / 100.0)
+        );
+        assert_eq!(
+            breakdown.suggested_price,
+            breakdown.total_cost + breakdown.total_for(CostKind::Markup)
+        );
+    }
+
+    #[test]
+    fn unit_prices_follow_the_tax_mode() {
+        let mut logic = CalculatorLogic::default();
+        logic.job.filaments[0].weight = 50.0;
+        logic.job.filaments[0].price_per_roll = Money::from_f64(20.0, Currency::EUR);
+        logic.job.print_time = 2.0;
+        logic.tax_rates[0].enabled = true;
+        logic.prices_include_tax = true;
+        logic.calculate_costs();
+        let breakdown = &logic.breakdown;
+        assert_eq!(breakdown.unit_price(), breakdown.gross_price);
+        assert_eq!(logic.price_breaks[0].unit_price, breakdown.gross_price);
+
+        logic.prices_include_tax = false;
+        logic.calculate_costs();
+        assert_eq!(logic.breakdown.unit_price(), logic.breakdown.suggested_price);
+    }
+
+    #[test]
+    fn base_currency_without_a_rate_is_refused() {
+        let mut logic = CalculatorLogic::default();
+        assert!(!logic.set_base_currency(Currency::from_code("KWD").unwrap()));
+        assert_eq!(logic.exchange_rates.base, Currency::EUR);
+        assert_eq!(logic.hourly_charge.currency(), Currency::EUR);
     }
 }
@@ -1,9 +1,21 @@
 // Suppress the terminal window on Windows release builds
 #![cfg_attr(windows, windows_subsystem = "windows")]
 
+mod breakdown;
+mod currency;
+mod exchange;
 mod filament_prices;
+mod flushing;
+mod labor;
 mod logic;
+mod materials;
+mod money;
+mod post_processing;
+mod pricing;
+mod printer;
+mod tariff;
 mod ui;
+mod units;
 
 use crate::ui::{CalculatorUI, load_logo};
 
@@ -22,11 +34,11 @@ fn main() -> Result<(), eframe::Error> {
         "FDM Cost Calculator",     // Window title
         options,                   // Window and rendering options
         Box::new(|cc| {
-            // Initialize the main application structure
-            let mut app = CalculatorUI::default();
-
-            // Load and assign the application logo
-            app.logo = load_logo(cc);
+            // Initialize the main application structure with the loaded logo
+            let app = CalculatorUI {
+                logo: load_logo(cc),
+                ..Default::default()
+            };
 
             // Return the initialized application instance
             Ok(Box::new(app))
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+/// Physical properties of a filament material family.
+#[derive(Clone, Copy)]
+pub struct MaterialProperties {
+    pub density: f32,        // Density in g/cm³
+    pub failure_rate: f32,   // Typical failure rate in percent per 10 print hours
+    pub nozzle_temp: f32,    // Typical nozzle temperature in °C
+    pub bed_temp: f32,       // Typical bed temperature in °C
+    pub needs_chamber: bool, // Whether the material is printed in a heated chamber
+}
+
+// Material families matched against material names, most specific first
+const MATERIALS: &[(&str, MaterialProperties)] = &[
+    (
+        "PETG",
+        MaterialProperties {
+            density: 1.27,
+            failure_rate: 4.0,
+            nozzle_temp: 240.0,
+            bed_temp: 80.0,
+            needs_chamber: false,
+        },
+    ),
+    (
+        "PLA",
+        MaterialProperties {
+            density: 1.24,
+            failure_rate: 3.0,
+            nozzle_temp: 215.0,
+            bed_temp: 60.0,
+            needs_chamber: false,
+        },
+    ),
+    (
+        "ABS",
+        MaterialProperties {
+            density: 1.04,
+            failure_rate: 8.0,
+            nozzle_temp: 250.0,
+            bed_temp: 100.0,
+            needs_chamber: true,
+        },
+    ),
+    (
+        "ASA",
+        MaterialProperties {
+            density: 1.07,
+            failure_rate: 7.0,
+            nozzle_temp: 255.0,
+            bed_temp: 100.0,
+            needs_chamber: true,
+        },
+    ),
+    (
+        "PC",
+        MaterialProperties {
+            density: 1.20,
+            failure_rate: 10.0,
+            nozzle_temp: 270.0,
+            bed_temp: 110.0,
+            needs_chamber: true,
+        },
+    ),
+    (
+        "PA",
+        MaterialProperties {
+            density: 1.14,
+            failure_rate: 10.0,
+            nozzle_temp: 280.0,
+            bed_temp: 90.0,
+            needs_chamber: true,
+        },
+    ),
+    (
+        "TPU",
+        MaterialProperties {
+            density: 1.21,
+            failure_rate: 8.0,
+            nozzle_temp: 225.0,
+            bed_temp: 45.0,
+            needs_chamber: false,
+        },
+    ),
+    (
+        "PVA",
+        MaterialProperties {
+            density: 1.23,
+            failure_rate: 10.0,
+            nozzle_temp: 215.0,
+            bed_temp: 60.0,
+            needs_chamber: false,
+        },
+    ),
+];
+
+// Used for custom and unrecognised materials
+const DEFAULT_MATERIAL: MaterialProperties = MaterialProperties {
+    density: 1.24,
+    failure_rate: 5.0,
+    nozzle_temp: 215.0,
+    bed_temp: 60.0,
+    needs_chamber: false,
+};
+
+/// Returns the properties of the material family a material name belongs to.
+pub fn material_properties(material: &str) -> MaterialProperties {
+    let material = material.to_uppercase();
+    MATERIALS
+        .iter()
+        .find(|(family, _)| material.contains(family))
+        .map(|(_, properties)| *properties)
+        .unwrap_or(DEFAULT_MATERIAL)
+}
+
+/// Filler or additive blended into a filament.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Additive {
+    None,
+    CarbonFiber, // Replaces the former "Carbon-Based" checkbox
+    GlassFiber,
+    MetalFill,
+    Glow,
+    Wood,
+}
+
+impl Additive {
+    pub const ALL: [Additive; 6] = [
+        Additive::None,
+        Additive::CarbonFiber,
+        Additive::GlassFiber,
+        Additive::MetalFill,
+        Additive::Glow,
+        Additive::Wood,
+    ];
+
+    /// Returns the display name of the additive.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Additive::None => "None",
+            Additive::CarbonFiber => "Carbon Fiber",
+            Additive::GlassFiber => "Glass Fiber",
+            Additive::MetalFill => "Metal-Fill",
+            Additive::Glow => "Glow-in-the-Dark",
+            Additive::Wood => "Wood-Fill",
+        }
+    }
+}
+
+/// Pricing and wear settings for an additive category.
+pub struct AdditiveSettings {
+    pub surcharge_per_kg: f32,   // Extra cost per kg of filament
+    pub nozzle_wear_factor: f32, // How many times faster abrasion-sensitive parts wear
+}
+
+/// Returns the default settings for each additive category.
+pub fn get_additive_settings() -> HashMap<Additive, AdditiveSettings> {
+    HashMap::from([
+        (Additive::None, AdditiveSettings { surcharge_per_kg: 0.0, nozzle_wear_factor: 1.0 }),
+        (Additive::CarbonFiber, AdditiveSettings { surcharge_per_kg: 10.0, nozzle_wear_factor: 4.0 }),
+        (Additive::GlassFiber, AdditiveSettings { surcharge_per_kg: 8.0, nozzle_wear_factor: 3.0 }),
+        (Additive::MetalFill, AdditiveSettings { surcharge_per_kg: 12.0, nozzle_wear_factor: 2.5 }),
+        (Additive::Glow, AdditiveSettings { surcharge_per_kg: 5.0, nozzle_wear_factor: 3.0 }),
+        (Additive::Wood, AdditiveSettings { surcharge_per_kg: 3.0, nozzle_wear_factor: 1.2 }),
+    ])
+}
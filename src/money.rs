@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+use crate::currency::Currency;
+
+/// An exact amount of money in a currency, stored as a whole number of minor units
+/// (e.g. cents).
+///
+/// Amounts computed in floating point enter as `Money` through `from_f32` or `scale`,
+/// which round half away from zero to the minor unit. From there on additions and
+/// subtractions are exact, so a total built from rounded lines always equals their sum.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Money {
+    minor_units: i64,
+    currency: Currency,
+}
+
+impl Money {
+    /// Returns zero in the given currency.
+    pub fn zero(currency: Currency) -> Self {
+        Self {
+            minor_units: 0,
+            currency,
+        }
+    }
+
+    /// Creates an amount from a whole number of minor units.
+    pub fn from_minor_units(minor_units: i64, currency: Currency) -> Self {
+        Self {
+            minor_units,
+            currency,
+        }
+    }
+
+    /// Rounds a floating-point amount to the nearest minor unit of the currency.
+    pub fn from_f32(amount: f32, currency: Currency) -> Self {
+        Self::from_f64(amount as f64, currency)
+    }
+
+    /// Rounds an amount in major units to the nearest minor unit of the currency.
+    pub fn from_f64(amount: f64, currency: Currency) -> Self {
+        let scale = 10f64.powi(currency.minor_units() as i32);
+        Self {
+            minor_units: (amount * scale).round() as i64,
+            currency,
+        }
+    }
+
+    /// Returns the amount as a whole number of minor units.
+    pub fn minor_units(&self) -> i64 {
+        self.minor_units
+    }
+
+    /// Returns the currency of the amount.
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the amount in major units (e.g. euros), for further calculations.
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Returns the amount in major units without losing precision on large amounts.
+    pub fn to_f64(self) -> f64 {
+        self.minor_units as f64 / 10f64.powi(self.currency.minor_units() as i32)
+    }
+
+    /// Returns whether the amount is zero.
+    pub fn is_zero(&self) -> bool {
+        self.minor_units == 0
+    }
+
+    /// Multiplies the amount by a factor, rounding the result to the minor unit.
+    pub fn scale(self, factor: f32) -> Self {
+        Self {
+            minor_units: (self.minor_units as f64 * factor as f64).round() as i64,
+            currency: self.currency,
+        }
+    }
+
+    /// Converts the amount to another currency at `rate` units of `to` per unit of the
+    /// amount's currency, rounding to the minor unit of `to`.
+    pub fn convert(self, to: Currency, rate: f32) -> Self {
+        if to == self.currency && rate == 1.0 {
+            return self;
+        }
+        Self::from_f64(self.to_f64() * rate as f64, to)
+    }
+
+    /// Divides the amount into `parts` equal shares, rounding each share to the minor unit.
+    pub fn per(self, parts: u32) -> Self {
+        self.scale(1.0 / parts.max(1) as f32)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        debug_assert_eq!(self.currency, other.currency, "adding amounts in different currencies");
+        Money {
+            minor_units: self.minor_units + other.minor_units,
+            currency: self.currency,
+        }
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        self + -other
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money {
+            minor_units: -self.minor_units,
+            currency: self.currency,
+        }
+    }
+}
+
+impl PartialOrd for Money {
+    /// Amounts in different currencies are not comparable.
+    fn partial_cmp(&self, other: &Money) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.minor_units.cmp(&other.minor_units))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_half_away_from_zero() {
+        assert_eq!(Money::from_f64(0.125, Currency::EUR).minor_units(), 13);
+        assert_eq!(Money::from_f64(-0.125, Currency::EUR).minor_units(), -13);
+        assert_eq!(Money::from_minor_units(5, Currency::EUR).scale(0.5).minor_units(), 3);
+        assert_eq!(Money::from_minor_units(-5, Currency::EUR).scale(0.5).minor_units(), -3);
+    }
+
+    #[test]
+    fn follows_the_minor_unit_of_the_currency() {
+        let kwd = Currency::from_code("KWD").unwrap();
+        assert_eq!(Money::from_f64(1234.5, Currency::JPY).minor_units(), 1235);
+        assert_eq!(Money::from_f64(1.2345, kwd).minor_units(), 1235);
+        assert_eq!(Money::from_minor_units(1235, kwd).to_f64(), 1.235);
+    }
+
+    #[test]
+    fn sums_are_exact() {
+        let cent = Money::from_f64(0.01, Currency::EUR);
+        let total = (0..1_000_000).fold(Money::zero(Currency::EUR), |total, _| total + cent);
+        assert_eq!(total, Money::from_f64(10_000.0, Currency::EUR));
+        assert_eq!(total - cent - total, -cent);
+    }
+
+    #[test]
+    fn splits_per_part() {
+        let price = Money::from_f64(10.0, Currency::EUR);
+        assert_eq!(price.per(3).minor_units(), 333);
+        assert_eq!(price.per(0), price);
+    }
+
+    #[test]
+    fn converts_between_currencies() {
+        let price = Money::from_f64(12.34, Currency::EUR);
+        assert_eq!(price.convert(Currency::JPY, 162.0), Money::from_minor_units(1999, Currency::JPY));
+        assert_eq!(price.convert(Currency::EUR, 1.0), price);
+    }
+
+    #[test]
+    fn compares_only_the_same_currency() {
+        let euro = Money::from_f64(1.0, Currency::EUR);
+        assert!(euro > Money::zero(Currency::EUR));
+        assert_eq!(euro.partial_cmp(&Money::from_f64(1.0, Currency::USD)), None);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::currency::Currency;
+use crate::money::Money;
+
+/// A reusable finishing operation such as sanding, painting or inserting threads.
+pub struct PostProcessOperation {
+    pub name: String,
+    pub role: usize,                     // Index of the labor role doing the work
+    pub minutes_per_part: f32,           // Attended minutes per part
+    pub consumables: String,             // Materials used up, e.g. primer or inserts
+    pub consumable_cost_per_part: Money, // Cost of the materials used per part
+}
+
+/// An operation from the catalog attached to a job.
+#[derive(Clone)]
+pub struct PostProcessStep {
+    pub operation: usize, // Index of the operation in the catalog
+    pub repeats: u32,     // Times the operation is done per part, e.g. coats or inserts
+}
+
+impl PostProcessStep {
+    /// Returns how often the operation is carried out for `parts` parts.
+    pub fn repetitions(&self, parts: u32) -> f32 {
+        (self.repeats * parts) as f32
+    }
+}
+
+/// Returns the default post-processing operation catalog.
+pub fn get_post_processing_operations() -> Vec<PostProcessOperation> {
+    [
+        ("Sanding", 1, 10.0, "Sandpaper", 0.15),
+        ("Priming", 1, 5.0, "Filler primer", 0.40),
+        ("Painting", 1, 15.0, "Paint", 0.80),
+        ("Vapor smoothing", 1, 5.0, "Acetone", 0.30),
+        ("Annealing", 0, 2.0, "", 0.0),
+        ("Heat-set insert", 1, 1.0, "Brass insert", 0.10),
+    ]
+    .into_iter()
+    .map(|(name, role, minutes, consumables, cost)| PostProcessOperation {
+        name: name.to_string(),
+        role,
+        minutes_per_part: minutes,
+        consumables: consumables.to_string(),
+        consumable_cost_per_part: Money::from_f32(cost, Currency::EUR),
+    })
+    .collect()
+}
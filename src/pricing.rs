@@ -0,0 +1,465 @@
+use crate::breakdown::CostBreakdown;
+use crate::currency::Currency;
+use crate::money::Money;
+
+/// Quantities shown in the price-break table.
+pub const PRICE_BREAK_QUANTITIES: [u32; 6] = [1, 5, 10, 25, 50, 100];
+
+/// How the profit on top of the total cost is specified.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PricingMode {
+    Markup, // Profit as a percentage of cost
+    Margin, // Profit as a percentage of the price
+}
+
+/// A step of the pricing pipeline. Stages are applied in the order of `ALL`: cost
+/// components first, then markup, adjustments, fees, rounding and tax.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PricingStage {
+    Materials,
+    Machine,
+    Labor,
+    PostProcessing,
+    Shipping,
+    Markup,
+    Adjustments,
+    Fees,
+    Rounding,
+    Tax,
+}
+
+impl PricingStage {
+    pub const ALL: [PricingStage; 10] = [
+        PricingStage::Materials,
+        PricingStage::Machine,
+        PricingStage::Labor,
+        PricingStage::PostProcessing,
+        PricingStage::Shipping,
+        PricingStage::Markup,
+        PricingStage::Adjustments,
+        PricingStage::Fees,
+        PricingStage::Rounding,
+        PricingStage::Tax,
+    ];
+
+    /// Returns the display name of the stage.
+    pub fn label(&self) -> &'static str {
+        match self {
+            PricingStage::Materials => "Materials",
+            PricingStage::Machine => "Machine",
+            PricingStage::Labor => "Labor",
+            PricingStage::PostProcessing => "Post-processing",
+            PricingStage::Shipping => "Shipping",
+            PricingStage::Markup => "Markup",
+            PricingStage::Adjustments => "Discounts, setup fee and minimum order",
+            PricingStage::Fees => "Channel and payment fees",
+            PricingStage::Rounding => "Rounding",
+            PricingStage::Tax => "Tax",
+        }
+    }
+
+    /// Returns whether the stage adds cost components, which markup may apply to.
+    pub fn is_cost(&self) -> bool {
+        matches!(
+            self,
+            PricingStage::Materials
+                | PricingStage::Machine
+                | PricingStage::Labor
+                | PricingStage::PostProcessing
+                | PricingStage::Shipping
+        )
+    }
+}
+
+/// A cost stage and whether the markup is charged on it.
+pub struct PipelineStage {
+    pub stage: PricingStage,
+    pub markup_applies: bool, // Whether the stage's costs are included in the markup base
+}
+
+/// Returns the default cost stages, all of them marked up.
+pub fn get_pricing_pipeline() -> Vec<PipelineStage> {
+    PricingStage::ALL
+        .into_iter()
+        .filter(PricingStage::is_cost)
+        .map(|stage| PipelineStage {
+            stage,
+            markup_applies: true,
+        })
+        .collect()
+}
+
+/// Markup, margin and earning rate implied by a given selling price.
+pub struct ReverseQuote {
+    pub target_price: Money,     // Price entered by the user, as quoted to the customer
+    pub net_revenue: Money,      // Price left after tax and fees
+    pub profit: Money,           // Net revenue minus total cost
+    pub markup_percentage: f32,  // Profit as a percentage of cost
+    pub margin_percentage: f32,  // Profit as a percentage of net revenue
+    pub profit_per_machine_hour: Money,
+}
+
+/// Returns profit as a percentage of `base`, or zero when there is no base.
+pub fn percentage_of(profit: Money, base: Money) -> f32 {
+    if base > Money::zero(base.currency()) {
+        profit.to_f32() / base.to_f32() * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// How suggested prices are rounded.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RoundingStrategy {
+    None,
+    Nearest,      // Nearest multiple of the step
+    Up,           // Next multiple of the step
+    Charm,        // Next price ending in the charm ending, e.g. 17.99
+    CurrencyStep, // Nearest multiple of the currency's smallest cash step, e.g. 0.05 CHF
+}
+
+impl RoundingStrategy {
+    pub const ALL: [RoundingStrategy; 5] = [
+        RoundingStrategy::None,
+        RoundingStrategy::Nearest,
+        RoundingStrategy::Up,
+        RoundingStrategy::Charm,
+        RoundingStrategy::CurrencyStep,
+    ];
+
+    /// Returns the display name of the strategy.
+    pub fn label(&self) -> &'static str {
+        match self {
+            RoundingStrategy::None => "No rounding",
+            RoundingStrategy::Nearest => "Round to nearest",
+            RoundingStrategy::Up => "Always round up",
+            RoundingStrategy::Charm => "Charm pricing",
+            RoundingStrategy::CurrencyStep => "Currency step",
+        }
+    }
+}
+
+/// Rounding applied to the quoted price after markup.
+#[derive(Clone, Copy)]
+pub struct RoundingPolicy {
+    pub strategy: RoundingStrategy,
+    pub step: f32,          // Step for nearest and round-up rounding, e.g. 0.05 or 0.50
+    pub charm_ending: f32,  // Ending for charm pricing, e.g. 0.99 or 0.95 (99 or 95 without decimals)
+}
+
+impl Default for RoundingPolicy {
+    fn default() -> Self {
+        Self {
+            strategy: RoundingStrategy::None,
+            step: 0.05,
+            charm_ending: 0.99,
+        }
+    }
+}
+
+impl RoundingPolicy {
+    /// Rounds a price according to the policy. Rounding works on whole minor units, so
+    /// steps and charm endings finer than the currency's minor unit are rounded to it.
+    ///
+    /// Charm endings apply to the fraction of a major unit, or, for currencies without
+    /// decimals such as JPY, to the last two digits (0.99 gives ¥1,299).
+    pub fn apply(&self, price: Money) -> Money {
+        let currency = price.currency();
+        let scale = 10i64.pow(currency.minor_units());
+        let to_units = |amount: f32| (amount as f64 * scale as f64).round() as i64;
+        let units = price.minor_units();
+        let rounded = match self.strategy {
+            RoundingStrategy::None => units,
+            RoundingStrategy::CurrencyStep => round_to_step(units, currency.cash_step(), false),
+            RoundingStrategy::Nearest => round_to_step(units, to_units(self.step), false),
+            RoundingStrategy::Up => round_to_step(units, to_units(self.step), true),
+            RoundingStrategy::Charm => {
+                let block = if currency.minor_units() == 0 { 100 } else { scale };
+                let ending = (self.charm_ending as f64 * block as f64).round() as i64;
+                let candidate = units.div_euclid(block) * block + ending;
+                if candidate >= units {
+                    candidate
+                } else {
+                    candidate + block
+                }
+            }
+        };
+        Money::from_minor_units(rounded, currency)
+    }
+}
+
+/// Rounds `units` to a multiple of `step`, to the nearest one (halves away from zero)
+/// or upwards.
+fn round_to_step(units: i64, step: i64, up: bool) -> i64 {
+    if step <= 0 {
+        return units;
+    }
+    let remainder = units.rem_euclid(step);
+    if remainder == 0 {
+        units
+    } else if up || remainder * 2 > step || (remainder * 2 == step && units > 0) {
+        units - remainder + step
+    } else {
+        units - remainder
+    }
+}
+
+/// Discount granted from a minimum order quantity upwards.
+#[derive(Clone, Copy)]
+pub struct DiscountTier {
+    pub min_quantity: u32,        // Smallest quantity the discount applies to
+    pub discount_percentage: f32, // Discount on the suggested price
+}
+
+/// Returns the discount percentage for a quantity, taken from the highest tier reached.
+pub fn discount_for(tiers: &[DiscountTier], quantity: u32) -> f32 {
+    tiers
+        .iter()
+        .filter(|tier| quantity >= tier.min_quantity)
+        .max_by_key(|tier| tier.min_quantity)
+        .map_or(0.0, |tier| tier.discount_percentage)
+}
+
+/// A named tax applied on top of the net price.
+pub struct TaxRate {
+    pub name: String,
+    pub rate_percentage: f32,
+    pub enabled: bool, // Whether the tax applies to quotes
+}
+
+/// Returns the default tax rates, all disabled.
+pub fn get_tax_rates() -> Vec<TaxRate> {
+    vec![
+        TaxRate { name: "VAT".to_string(), rate_percentage: 20.0, enabled: false },
+        TaxRate { name: "Sales tax".to_string(), rate_percentage: 8.0, enabled: false },
+    ]
+}
+
+/// Fees a sales channel takes from each order.
+pub struct SalesChannel {
+    pub name: String,
+    pub fee_percentage: f32,          // Percentage taken from the item price
+    pub fixed_fee: Money,             // Fixed listing or transaction fee per order
+    pub shipping_fee_percentage: f32, // Percentage taken from the shipping charged
+}
+
+impl SalesChannel {
+    /// Returns the channel fees on an order with the given list price and shipping charge,
+    /// which must be in the currency of the fixed fee.
+    pub fn fees(&self, list_price: Money, shipping: Money) -> Money {
+        (list_price - shipping).scale(self.fee_percentage / 100.0)
+            + shipping.scale(self.shipping_fee_percentage / 100.0)
+            + self.fixed_fee
+    }
+}
+
+/// Fees a payment processor takes from each payment.
+pub struct PaymentMethod {
+    pub name: String,
+    pub fee_percentage: f32,                 // Percentage of the amount charged
+    pub fixed_fee: Money,                    // Fixed fee per payment
+    pub currency_conversion_percentage: f32, // Extra percentage for foreign-currency payments
+}
+
+impl PaymentMethod {
+    /// Returns the processor fee on a payment of `charged` (including tax), which must be
+    /// in the currency of the fixed fee.
+    pub fn fee(&self, charged: Money) -> Money {
+        charged.scale((self.fee_percentage + self.currency_conversion_percentage) / 100.0) + self.fixed_fee
+    }
+}
+
+/// Solves for the list price (excluding tax) that leaves `net` after sales channel and
+/// payment fees. `shipping` is the part of the price charged as shipping and `tax_rate`
+/// the total tax rate, since payment fees are taken from the amount charged including tax.
+pub fn solve_list_price(
+    net: Money,
+    shipping: Money,
+    tax_rate: f32,
+    channel: Option<&SalesChannel>,
+    payment: Option<&PaymentMethod>,
+) -> Money {
+    // Fees are linear in the list price: fees(P) = P * variable + fixed
+    let (mut variable, mut fixed) = (0.0, Money::zero(net.currency()));
+    if let Some(channel) = channel {
+        variable += channel.fee_percentage / 100.0;
+        fixed += shipping.scale((channel.shipping_fee_percentage - channel.fee_percentage) / 100.0)
+            + channel.fixed_fee;
+    }
+    if let Some(payment) = payment {
+        variable += (payment.fee_percentage + payment.currency_conversion_percentage) / 100.0
+            * (1.0 + tax_rate);
+        fixed += payment.fixed_fee;
+    }
+    (net + fixed).scale(1.0 / (1.0 - variable).max(0.01))
+}
+
+/// Returns the default sales channel profiles.
+pub fn get_sales_channels() -> Vec<SalesChannel> {
+    let eur = |amount| Money::from_f32(amount, Currency::EUR);
+    vec![
+        SalesChannel {
+            name: "Own webshop".to_string(),
+            fee_percentage: 0.0,
+            fixed_fee: eur(0.0),
+            shipping_fee_percentage: 0.0,
+        },
+        SalesChannel {
+            name: "Etsy".to_string(),
+            fee_percentage: 6.5,
+            fixed_fee: eur(0.20),
+            shipping_fee_percentage: 6.5,
+        },
+        SalesChannel {
+            name: "eBay".to_string(),
+            fee_percentage: 13.25,
+            fixed_fee: eur(0.30),
+            shipping_fee_percentage: 13.25,
+        },
+        SalesChannel {
+            name: "Amazon".to_string(),
+            fee_percentage: 15.0,
+            fixed_fee: eur(0.99),
+            shipping_fee_percentage: 15.0,
+        },
+    ]
+}
+
+/// Returns the default payment method profiles.
+pub fn get_payment_methods() -> Vec<PaymentMethod> {
+    let eur = |amount| Money::from_f32(amount, Currency::EUR);
+    vec![
+        PaymentMethod {
+            name: "Bank transfer".to_string(),
+            fee_percentage: 0.0,
+            fixed_fee: eur(0.0),
+            currency_conversion_percentage: 0.0,
+        },
+        PaymentMethod {
+            name: "Card".to_string(),
+            fee_percentage: 1.5,
+            fixed_fee: eur(0.25),
+            currency_conversion_percentage: 0.0,
+        },
+        PaymentMethod {
+            name: "Card (foreign currency)".to_string(),
+            fee_percentage: 1.5,
+            fixed_fee: eur(0.25),
+            currency_conversion_percentage: 2.0,
+        },
+        PaymentMethod {
+            name: "PayPal".to_string(),
+            fee_percentage: 2.99,
+            fixed_fee: eur(0.39),
+            currency_conversion_percentage: 0.0,
+        },
+        PaymentMethod {
+            name: "PayPal (foreign currency)".to_string(),
+            fee_percentage: 2.99,
+            fixed_fee: eur(0.39),
+            currency_conversion_percentage: 3.0,
+        },
+    ]
+}
+
+/// One row of the price-break table.
+pub struct PriceBreak {
+    pub quantity: u32,
+    pub unit_cost: Money,
+    pub unit_price: Money,      // Quoted price per part, including tax in tax-inclusive mode
+    pub margin_percentage: f32, // Profit as a share of the net revenue
+}
+
+impl PriceBreak {
+    /// Creates a row from the breakdown of a job at the row's quantity.
+    pub fn from_breakdown(breakdown: &CostBreakdown) -> Self {
+        Self {
+            quantity: breakdown.quantity,
+            unit_cost: breakdown.unit_cost(),
+            unit_price: breakdown.unit_price(),
+            margin_percentage: percentage_of(breakdown.profit(), breakdown.net_revenue()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(strategy: RoundingStrategy, step: f32, charm_ending: f32) -> RoundingPolicy {
+        RoundingPolicy { strategy, step, charm_ending }
+    }
+
+    fn price(amount: f64, currency: Currency) -> Money {
+        Money::from_f64(amount, currency)
+    }
+
+    #[test]
+    fn rounds_to_the_nearest_step() {
+        let nearest = policy(RoundingStrategy::Nearest, 0.50, 0.99);
+        assert_eq!(nearest.apply(price(17.24, Currency::EUR)), price(17.0, Currency::EUR));
+        assert_eq!(nearest.apply(price(17.25, Currency::EUR)), price(17.5, Currency::EUR));
+        assert_eq!(nearest.apply(price(17.5, Currency::EUR)), price(17.5, Currency::EUR));
+    }
+
+    #[test]
+    fn rounds_up_to_the_next_step() {
+        let up = policy(RoundingStrategy::Up, 0.05, 0.99);
+        assert_eq!(up.apply(price(17.01, Currency::EUR)), price(17.05, Currency::EUR));
+        assert_eq!(up.apply(price(17.05, Currency::EUR)), price(17.05, Currency::EUR));
+    }
+
+    #[test]
+    fn rounds_to_the_cash_step_of_the_currency() {
+        let step = policy(RoundingStrategy::CurrencyStep, 0.50, 0.99);
+        assert_eq!(step.apply(price(17.02, Currency::CHF)), price(17.0, Currency::CHF));
+        assert_eq!(step.apply(price(17.03, Currency::CHF)), price(17.05, Currency::CHF));
+        assert_eq!(step.apply(price(17.49, Currency::SEK)), price(17.0, Currency::SEK));
+        assert_eq!(step.apply(price(17.02, Currency::EUR)), price(17.02, Currency::EUR));
+    }
+
+    #[test]
+    fn charm_prices_end_in_the_ending() {
+        let charm = policy(RoundingStrategy::Charm, 0.05, 0.99);
+        assert_eq!(charm.apply(price(17.20, Currency::EUR)), price(17.99, Currency::EUR));
+        assert_eq!(charm.apply(price(17.99, Currency::EUR)), price(17.99, Currency::EUR));
+        assert_eq!(charm.apply(price(18.00, Currency::EUR)), price(18.99, Currency::EUR));
+    }
+
+    #[test]
+    fn charm_prices_without_decimals_end_in_the_last_two_digits() {
+        let charm = policy(RoundingStrategy::Charm, 0.05, 0.99);
+        assert_eq!(charm.apply(price(1234.0, Currency::JPY)), price(1299.0, Currency::JPY));
+        assert_eq!(charm.apply(price(1300.0, Currency::JPY)), price(1399.0, Currency::JPY));
+        let ninety_five = policy(RoundingStrategy::Charm, 0.05, 0.95);
+        assert_eq!(ninety_five.apply(price(1296.0, Currency::JPY)), price(1395.0, Currency::JPY));
+    }
+
+    #[test]
+    fn list_price_leaves_the_net_after_fees() {
+        let channels = get_sales_channels();
+        let payments = get_payment_methods();
+        let net = price(100.0, Currency::EUR);
+        let shipping = price(5.0, Currency::EUR);
+        for channel in &channels {
+            for payment in &payments {
+                let list = solve_list_price(net, shipping, 0.2, Some(channel), Some(payment));
+                let fees = channel.fees(list, shipping) + payment.fee(list.scale(1.2));
+                let left = (list - fees - net).minor_units();
+                assert!(left.abs() <= 1, "{} and {} leave {} cents", channel.name, payment.name, left);
+            }
+        }
+    }
+
+    #[test]
+    fn list_price_without_fees_is_the_net() {
+        let net = price(42.42, Currency::EUR);
+        assert_eq!(solve_list_price(net, price(5.0, Currency::EUR), 0.2, None, None), net);
+    }
+
+    #[test]
+    fn no_rounding_keeps_the_price() {
+        let none = RoundingPolicy::default();
+        assert_eq!(none.apply(price(17.23, Currency::EUR)), price(17.23, Currency::EUR));
+    }
+}
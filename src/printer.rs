@@ -0,0 +1,259 @@
+use crate::currency::Currency;
+use crate::money::Money;
+use crate::tariff::LoadSegment;
+
+// Room temperature the heaters work against, in °C
+const AMBIENT_TEMP: f32 = 22.0;
+
+// Time assumed for heating up before each plate, in hours
+const WARMUP_HOURS: f32 = 0.1;
+
+/// Temperatures a job is printed at.
+pub struct PrintConditions {
+    pub nozzle_temp: f32,     // Nozzle temperature in °C
+    pub bed_temp: f32,        // Bed temperature in °C
+    pub heated_chamber: bool, // Whether the chamber heater runs
+}
+
+/// Power draw of a printer while heating up, printing and idling.
+pub struct PowerProfile {
+    pub base_watts: f32,                  // Electronics, motors and fans while printing
+    pub bed_watts_per_degree: f32,        // Steady bed draw per °C above ambient
+    pub hotend_watts_per_degree: f32,     // Steady hotend draw per °C above ambient
+    pub bed_warmup_wh_per_degree: f32,    // Energy to heat the bed by one °C
+    pub hotend_warmup_wh_per_degree: f32, // Energy to heat the hotend by one °C
+    pub chamber_heater_watts: f32,        // Chamber heater draw, zero when there is none
+    pub standby_watts: f32,               // Idle draw between plates
+    pub idle_minutes_per_plate: f32,      // Idle time between plates for cool-down and removal
+}
+
+impl PowerProfile {
+    /// Returns the energy in Wh needed to heat up before a plate.
+    pub fn warmup_wh(&self, conditions: &PrintConditions) -> f32 {
+        (conditions.bed_temp - AMBIENT_TEMP).max(0.0) * self.bed_warmup_wh_per_degree
+            + (conditions.nozzle_temp - AMBIENT_TEMP).max(0.0) * self.hotend_warmup_wh_per_degree
+    }
+
+    /// Returns the steady-state draw in watts while printing.
+    pub fn printing_watts(&self, conditions: &PrintConditions) -> f32 {
+        let chamber = if conditions.heated_chamber {
+            self.chamber_heater_watts
+        } else {
+            0.0
+        };
+        self.base_watts
+            + (conditions.bed_temp - AMBIENT_TEMP).max(0.0) * self.bed_watts_per_degree
+            + (conditions.nozzle_temp - AMBIENT_TEMP).max(0.0) * self.hotend_watts_per_degree
+            + chamber
+    }
+
+    /// Returns the load over time for printing `plates` plates of `hours_per_plate` each,
+    /// counting a partly filled last plate by its share of the print time.
+    pub fn load(
+        &self,
+        conditions: &PrintConditions,
+        plates: f32,
+        hours_per_plate: f32,
+    ) -> Vec<LoadSegment> {
+        let mut load = Vec::new();
+        let plate_count = plates.ceil() as usize;
+        for plate in 0..plate_count {
+            let share = (plates - plate as f32).min(1.0);
+            if plate > 0 {
+                load.push(LoadSegment {
+                    hours: self.idle_minutes_per_plate / 60.0,
+                    watts: self.standby_watts,
+                });
+            }
+            load.push(LoadSegment {
+                hours: WARMUP_HOURS,
+                watts: self.warmup_wh(conditions) / WARMUP_HOURS,
+            });
+            load.push(LoadSegment {
+                hours: hours_per_plate * share,
+                watts: self.printing_watts(conditions),
+            });
+        }
+        load
+    }
+}
+
+impl Default for PowerProfile {
+    fn default() -> Self {
+        Self {
+            base_watts: 40.0,
+            bed_watts_per_degree: 1.2,
+            hotend_watts_per_degree: 0.12,
+            bed_warmup_wh_per_degree: 0.8,
+            hotend_warmup_wh_per_degree: 0.05,
+            chamber_heater_watts: 0.0,
+            standby_watts: 8.0,
+            idle_minutes_per_plate: 10.0,
+        }
+    }
+}
+
+/// A replaceable printer part amortised over its service life.
+pub struct ConsumablePart {
+    pub name: String,
+    pub cost: Money,              // Replacement cost of the part
+    pub service_life_hours: f32,  // Print hours before the part is replaced
+    pub abrasion_sensitive: bool, // Whether abrasive filaments shorten the service life
+}
+
+impl ConsumablePart {
+    /// Returns the share of the part's cost used up in `hours` print hours, with the
+    /// service life divided by `wear_factor` for parts that wear faster with abrasive
+    /// filaments.
+    pub fn cost(&self, hours: f32, wear_factor: f32) -> Money {
+        let life = if self.abrasion_sensitive {
+            self.service_life_hours / wear_factor.max(1.0)
+        } else {
+            self.service_life_hours
+        };
+        if life <= 0.0 {
+            return Money::zero(self.cost.currency());
+        }
+        self.cost.scale(hours / life)
+    }
+}
+
+impl Default for ConsumablePart {
+    fn default() -> Self {
+        Self {
+            name: "Custom Part".to_string(),
+            cost: Money::zero(Currency::EUR),
+            service_life_hours: 1000.0,
+            abrasion_sensitive: false,
+        }
+    }
+}
+
+/// Ownership costs and power draw of a single printer.
+pub struct PrinterProfile {
+    pub name: String,
+    pub purchase_price: Money,     // Purchase price of the printer
+    pub residual_value: Money,     // Expected resale value at end of life
+    pub lifetime_hours: f32,       // Expected print hours over the printer's life
+    pub yearly_maintenance: Money, // Yearly maintenance budget
+    pub yearly_print_hours: f32,   // Print hours per year, used to spread the maintenance budget
+    pub power: PowerProfile,       // Power draw while heating, printing and idling
+    pub failure_rate: f32,         // Failure rate in percent per 10 print hours
+    pub consumables: Vec<ConsumablePart>, // Wear parts replaced over the printer's life
+}
+
+impl PrinterProfile {
+    /// Returns the depreciation over `hours` print hours.
+    pub fn depreciation(&self, hours: f32) -> Money {
+        let loss = self.purchase_price - self.residual_value;
+        let zero = Money::zero(loss.currency());
+        if self.lifetime_hours <= 0.0 || loss < zero {
+            return zero;
+        }
+        loss.scale(hours / self.lifetime_hours)
+    }
+
+    /// Returns the share of the maintenance budget spent over `hours` print hours.
+    pub fn maintenance(&self, hours: f32) -> Money {
+        if self.yearly_print_hours <= 0.0 {
+            return Money::zero(self.yearly_maintenance.currency());
+        }
+        self.yearly_maintenance.scale(hours / self.yearly_print_hours)
+    }
+
+    /// Returns the combined wear and tear cost of `hours` print hours.
+    pub fn wear_cost(&self, hours: f32) -> Money {
+        self.depreciation(hours) + self.maintenance(hours)
+    }
+}
+
+impl Default for PrinterProfile {
+    fn default() -> Self {
+        Self {
+            name: "Custom Printer".to_string(),
+            purchase_price: Money::from_f32(500.0, Currency::EUR),
+            residual_value: Money::from_f32(50.0, Currency::EUR),
+            lifetime_hours: 5000.0,
+            yearly_maintenance: Money::from_f32(50.0, Currency::EUR),
+            yearly_print_hours: 1000.0,
+            power: PowerProfile::default(),
+            failure_rate: 5.0,
+            consumables: default_consumables(),
+        }
+    }
+}
+
+/// Returns the built-in printer profiles.
+pub fn get_printer_profiles() -> Vec<PrinterProfile> {
+    let eur = |amount| Money::from_f32(amount, Currency::EUR);
+    vec![
+        PrinterProfile {
+            name: "Bedslinger".to_string(),
+            purchase_price: eur(300.0),
+            residual_value: eur(50.0),
+            lifetime_hours: 5000.0,
+            yearly_maintenance: eur(40.0),
+            yearly_print_hours: 1000.0,
+            power: PowerProfile::default(),
+            failure_rate: 5.0,
+            consumables: default_consumables(),
+        },
+        PrinterProfile {
+            name: "Enclosed CoreXY".to_string(),
+            purchase_price: eur(1500.0),
+            residual_value: eur(300.0),
+            lifetime_hours: 8000.0,
+            yearly_maintenance: eur(100.0),
+            yearly_print_hours: 2000.0,
+            power: PowerProfile {
+                base_watts: 60.0,
+                bed_watts_per_degree: 1.0,
+                hotend_watts_per_degree: 0.12,
+                bed_warmup_wh_per_degree: 1.2,
+                hotend_warmup_wh_per_degree: 0.05,
+                chamber_heater_watts: 0.0,
+                standby_watts: 10.0,
+                idle_minutes_per_plate: 10.0,
+            },
+            failure_rate: 3.0,
+            consumables: default_consumables(),
+        },
+    ]
+}
+
+/// Returns a typical set of consumable parts for a new printer profile.
+fn default_consumables() -> Vec<ConsumablePart> {
+    let eur = |amount| Money::from_f32(amount, Currency::EUR);
+    vec![
+        ConsumablePart {
+            name: "Nozzle".to_string(),
+            cost: eur(8.0),
+            service_life_hours: 600.0,
+            abrasion_sensitive: true,
+        },
+        ConsumablePart {
+            name: "PEI sheet".to_string(),
+            cost: eur(35.0),
+            service_life_hours: 1500.0,
+            abrasion_sensitive: false,
+        },
+        ConsumablePart {
+            name: "Belts".to_string(),
+            cost: eur(15.0),
+            service_life_hours: 3000.0,
+            abrasion_sensitive: false,
+        },
+        ConsumablePart {
+            name: "Hotend heatbreak".to_string(),
+            cost: eur(20.0),
+            service_life_hours: 2000.0,
+            abrasion_sensitive: true,
+        },
+        ConsumablePart {
+            name: "PTFE tube".to_string(),
+            cost: eur(5.0),
+            service_life_hours: 1000.0,
+            abrasion_sensitive: true,
+        },
+    ]
+}
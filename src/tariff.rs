@@ -0,0 +1,315 @@
+// Hours in a day and in a week
+const DAY_HOURS: f32 = 24.0;
+const WEEK_HOURS: f32 = 7.0 * DAY_HOURS;
+
+// Granularity of the cheapest start time search in hours
+const START_SEARCH_STEP: f32 = 0.25;
+
+pub const DAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Formats hours since Monday 00:00 as e.g. "Tue 22:30".
+pub fn format_week_time(time: f32) -> String {
+    let time = time.rem_euclid(WEEK_HOURS);
+    let day = (time / DAY_HOURS) as usize % 7;
+    let minutes = ((time % DAY_HOURS) * 60.0).round() as u32;
+    format!("{} {:02}:{:02}", DAY_NAMES[day], minutes / 60 % 24, minutes % 60)
+}
+
+/// A period of constant power draw.
+pub struct LoadSegment {
+    pub hours: f32, // Duration of the segment
+    pub watts: f32, // Power drawn during the segment
+}
+
+/// Days of the week a time band applies to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DayRule {
+    EveryDay,
+    Weekdays,
+    Weekends,
+}
+
+impl DayRule {
+    pub const ALL: [DayRule; 3] = [DayRule::EveryDay, DayRule::Weekdays, DayRule::Weekends];
+
+    /// Returns the display name of the rule.
+    pub fn label(&self) -> &'static str {
+        match self {
+            DayRule::EveryDay => "Every day",
+            DayRule::Weekdays => "Weekdays",
+            DayRule::Weekends => "Weekends",
+        }
+    }
+
+    /// Returns whether the rule covers a day (0 = Monday).
+    fn covers(&self, day: usize) -> bool {
+        match self {
+            DayRule::EveryDay => true,
+            DayRule::Weekdays => day < 5,
+            DayRule::Weekends => day >= 5,
+        }
+    }
+}
+
+/// A rate applying between two hours of the day, wrapping past midnight when the end
+/// hour is before the start hour (e.g. 22 to 6).
+pub struct TimeBand {
+    pub name: String,
+    pub days: DayRule,
+    pub start_hour: f32,
+    pub end_hour: f32,
+    pub rate: f32, // Rate per kWh
+}
+
+impl TimeBand {
+    /// Returns whether the band covers a time given in hours since Monday 00:00. The
+    /// day rule is checked against the day the time falls on.
+    fn covers(&self, time: f32) -> bool {
+        let day = (time / DAY_HOURS) as usize % 7;
+        let hour = time % DAY_HOURS;
+        let in_hours = if self.start_hour <= self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        };
+        in_hours && self.days.covers(day)
+    }
+}
+
+/// A consumption block of a tiered tariff.
+pub struct KwhTier {
+    pub up_to_kwh: f32, // Upper end of the block in kWh per billing period
+    pub rate: f32,      // Rate per kWh within the block
+}
+
+/// Electricity tariff with optional time-of-use bands and consumption tiers.
+///
+/// Time bands take precedence; outside them the rate comes from the tier the billing
+/// period's consumption has reached, or the base rate when no tiers are defined.
+pub struct Tariff {
+    pub base_rate: f32,       // Rate per kWh outside bands and tiers
+    pub bands: Vec<TimeBand>, // Time-of-use rates
+    pub tiers: Vec<KwhTier>,  // Consumption blocks in ascending order
+    pub period_kwh_used: f32, // kWh already used in the billing period, for tiers
+}
+
+impl Default for Tariff {
+    fn default() -> Self {
+        Self {
+            base_rate: 0.26,
+            bands: Vec::new(),
+            tiers: Vec::new(),
+            period_kwh_used: 0.0,
+        }
+    }
+}
+
+impl Tariff {
+    /// Returns the rate at a time with `used_kwh` consumed in the billing period, and the
+    /// kWh left until the next tier starts.
+    fn rate_at(&self, time: f32, used_kwh: f32) -> (f32, f32) {
+        if let Some(band) = self.bands.iter().find(|band| band.covers(time)) {
+            return (band.rate, f32::INFINITY);
+        }
+        match self.tiers.iter().position(|tier| used_kwh < tier.up_to_kwh) {
+            Some(index) if index + 1 < self.tiers.len() => {
+                (self.tiers[index].rate, self.tiers[index].up_to_kwh - used_kwh)
+            }
+            _ => (self.tiers.last().map_or(self.base_rate, |tier| tier.rate), f32::INFINITY),
+        }
+    }
+
+    /// Returns the next time after `time` at which a band may start or end.
+    fn next_boundary(&self, time: f32) -> f32 {
+        let day_start = (time / DAY_HOURS).floor() * DAY_HOURS;
+        self.bands
+            .iter()
+            .flat_map(|band| [band.start_hour, band.end_hour])
+            .map(|hour| day_start + hour)
+            .filter(|&boundary| boundary > time + 1e-4)
+            .fold(day_start + DAY_HOURS, f32::min)
+    }
+
+    /// Returns the energy in kWh and its cost for a load running back to back from
+    /// `start`, given in hours since Monday 00:00.
+    pub fn energy_cost(&self, start: f32, load: &[LoadSegment]) -> (f32, f32) {
+        if self.bands.is_empty() {
+            // Without time bands the rate only depends on consumption, so the whole load
+            // can be priced as one block instead of segment by segment
+            let kwh: f32 = load
+                .iter()
+                .map(|segment| segment.watts.max(0.0) / 1000.0 * segment.hours.max(0.0))
+                .sum();
+            return (kwh, self.consumption_cost(kwh));
+        }
+        // The clock is kept as a position within the week, so it stays precise enough for
+        // small steps however long the job runs
+        let week = WEEK_HOURS as f64;
+        let mut time = (start as f64).rem_euclid(week);
+        let (mut used, mut cost) = (self.period_kwh_used as f64, 0.0f64);
+        for segment in load {
+            let kw = segment.watts.max(0.0) as f64 / 1000.0;
+            let mut left = segment.hours.max(0.0) as f64;
+            while left > 1e-4 && kw > 0.0 {
+                let (rate, tier_left) = self.rate_at(time as f32, used as f32);
+                let step = (self.next_boundary(time as f32) as f64 - time)
+                    .min(left)
+                    .min(tier_left as f64 / kw)
+                    .max(1e-4);
+                if time + step == time {
+                    break;
+                }
+                cost += kw * step * rate as f64;
+                used += kw * step;
+                left -= step;
+                time = (time + step).rem_euclid(week);
+            }
+            time = (time + left.max(0.0)).rem_euclid(week);
+        }
+        ((used - self.period_kwh_used as f64) as f32, cost as f32)
+    }
+
+    /// Returns the cost of `kwh` consumed on top of the billing period's usage, outside
+    /// any time band.
+    fn consumption_cost(&self, kwh: f32) -> f32 {
+        let (mut used, mut left, mut cost) = (self.period_kwh_used, kwh, 0.0);
+        while left > 0.0 {
+            let (rate, tier_left) = self.rate_at(0.0, used);
+            let step = left.min(tier_left);
+            cost += step * rate;
+            used += step;
+            left -= step;
+        }
+        cost
+    }
+
+    /// Finds the start time within 24 hours of `from` with the lowest energy cost,
+    /// returning the start time and the cost.
+    pub fn cheapest_start(&self, from: f32, load: &[LoadSegment]) -> (f32, f32) {
+        if self.bands.is_empty() {
+            // Every start costs the same without time bands
+            return (from, self.energy_cost(from, load).1);
+        }
+        let steps = (DAY_HOURS / START_SEARCH_STEP) as usize;
+        (0..=steps)
+            .map(|i| {
+                let start = (from + i as f32 * START_SEARCH_STEP) % WEEK_HOURS;
+                (start, self.energy_cost(start, load).1)
+            })
+            .fold((from, f32::INFINITY), |best, candidate| {
+                // Keep the earliest start on ties
+                if candidate.1 < best.1 - 1e-6 {
+                    candidate
+                } else {
+                    best
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn band(days: DayRule, start_hour: f32, end_hour: f32, rate: f32) -> TimeBand {
+        TimeBand { name: "Band".to_string(), days, start_hour, end_hour, rate }
+    }
+
+    fn kilowatt(hours: f32) -> Vec<LoadSegment> {
+        vec![LoadSegment { hours, watts: 1000.0 }]
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "{} is not {}", actual, expected);
+    }
+
+    #[test]
+    fn charges_the_base_rate_without_bands() {
+        let tariff = Tariff { base_rate: 0.30, ..Tariff::default() };
+        let (kwh, cost) = tariff.energy_cost(8.0, &kilowatt(2.0));
+        assert_close(kwh, 2.0);
+        assert_close(cost, 0.60);
+    }
+
+    #[test]
+    fn splits_the_load_at_band_boundaries() {
+        let tariff = Tariff {
+            base_rate: 0.30,
+            bands: vec![band(DayRule::EveryDay, 22.0, 6.0, 0.10)],
+            ..Tariff::default()
+        };
+        // Monday 21:00 to 23:00: one hour at the base rate, one in the night band
+        let (kwh, cost) = tariff.energy_cost(21.0, &kilowatt(2.0));
+        assert_close(kwh, 2.0);
+        assert_close(cost, 0.40);
+        // Tuesday 05:30 to 06:30, leaving the band that wrapped past midnight
+        let (_, cost) = tariff.energy_cost(24.0 + 5.5, &kilowatt(1.0));
+        assert_close(cost, 0.20);
+    }
+
+    #[test]
+    fn applies_day_rules_across_midnight() {
+        let tariff = Tariff {
+            base_rate: 0.30,
+            bands: vec![band(DayRule::Weekends, 0.0, 24.0, 0.10)],
+            ..Tariff::default()
+        };
+        // Friday 23:00 to Saturday 01:00
+        let (_, cost) = tariff.energy_cost(4.0 * 24.0 + 23.0, &kilowatt(2.0));
+        assert_close(cost, 0.40);
+        // Sunday 23:00 to Monday 01:00, wrapping around the week
+        let (_, cost) = tariff.energy_cost(6.0 * 24.0 + 23.0, &kilowatt(2.0));
+        assert_close(cost, 0.40);
+    }
+
+    #[test]
+    fn moves_through_consumption_tiers() {
+        let mut tariff = Tariff {
+            tiers: vec![
+                KwhTier { up_to_kwh: 1.0, rate: 0.10 },
+                KwhTier { up_to_kwh: 100.0, rate: 0.50 },
+            ],
+            period_kwh_used: 0.5,
+            ..Tariff::default()
+        };
+        let (_, cost) = tariff.energy_cost(8.0, &kilowatt(2.0));
+        assert_close(cost, 0.5 * 0.10 + 1.5 * 0.50);
+
+        // A band that never applies switches to the time-stepped path, with the same result
+        tariff.bands.push(band(DayRule::Weekends, 0.0, 1.0, 0.01));
+        let (_, stepped) = tariff.energy_cost(8.0, &kilowatt(2.0));
+        assert_close(stepped, cost);
+    }
+
+    #[test]
+    fn long_jobs_with_fractional_bands_finish() {
+        let tariff = Tariff {
+            base_rate: 0.30,
+            bands: vec![band(DayRule::EveryDay, 22.3, 6.0, 0.10)],
+            ..Tariff::default()
+        };
+        // 300 plates of 20 hours, well past the hours an f32 clock can step through
+        let load: Vec<LoadSegment> = (0..300).flat_map(|_| kilowatt(20.0)).collect();
+        let (kwh, cost) = tariff.energy_cost(8.0, &load);
+        assert!((kwh - 6000.0).abs() < 0.5, "{} kWh", kwh);
+        assert!(cost > 6000.0 * 0.10 && cost < 6000.0 * 0.30, "costs {}", cost);
+    }
+
+    #[test]
+    fn suggests_the_cheapest_start() {
+        let tariff = Tariff {
+            base_rate: 0.30,
+            bands: vec![band(DayRule::EveryDay, 22.0, 6.0, 0.10)],
+            ..Tariff::default()
+        };
+        let (start, cost) = tariff.cheapest_start(12.0, &kilowatt(2.0));
+        assert!((22.0..=28.0).contains(&start), "starts at {}", format_week_time(start));
+        assert_close(cost, 0.20);
+    }
+
+    #[test]
+    fn formats_week_times() {
+        assert_eq!(format_week_time(24.0 + 22.5), "Tue 22:30");
+        assert_eq!(format_week_time(WEEK_HOURS + 1.0), "Mon 01:00");
+    }
+}
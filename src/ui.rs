@@ -1,269 +1,716 @@
 use eframe::egui;
 use eframe::epaint::TextureHandle;
 use egui::Grid;
-use crate::logic::{CalculatorLogic, Currency};
+use crate::breakdown::CostKind;
+use crate::exchange::ExchangeRates;
+use crate::labor::{LaborPhase, LaborRole, LaborScope};
+use crate::currency::{Currency, NumberLocale, LOCALES};
+use crate::logic::{CalculatorLogic, FILAMENT_DIAMETERS, MAX_QUANTITY};
+use crate::materials::Additive;
+use crate::money::Money;
+use crate::post_processing::{PostProcessOperation, PostProcessStep};
+use crate::pricing::{
+    DiscountTier, PaymentMethod, PricingMode, PricingStage, RoundingStrategy, SalesChannel,
+    TaxRate,
+};
+use crate::printer::{ConsumablePart, PrinterProfile};
+use crate::tariff::{format_week_time, DayRule, KwhTier, TimeBand, DAY_NAMES};
+use crate::units::{FilamentQuantity, LengthUnit, UnitSettings, WeightUnit};
 
+#[derive(Default)]
 pub struct CalculatorUI {
     pub logic: CalculatorLogic,
     pub logo: Option<TextureHandle>, // Texture handle for the logo
     pub show_help: bool,             // Whether to show the help dialog
+    pub show_settings: bool,         // Whether to show the settings window
     pub is_multi_color: bool,        // Track toggle state
-}
-
-impl Default for CalculatorUI {
-    fn default() -> Self {
-        Self {
-            logic: CalculatorLogic::default(),
-            logo: None,
-            show_help: false,
-            is_multi_color: false, // Default to single-color mode
-        }
-    }
+    pub flush_sequence: String,      // Tool change sequence typed by the user
+    pub flush_sequence_invalid: bool, // Whether the last sequence failed to parse
+    pub exchange_rates_path: String, // File to load exchange rates from
+    pub exchange_rates_status: String, // Result of the last exchange-rate change
 }
 
 impl eframe::App for CalculatorUI {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let locale = self.logic.locale;
+        let units = self.logic.units;
         egui::CentralPanel::default().show(ctx, |ui| {
-            // App heading and currency switcher
-            ui.horizontal(|ui| {
-                ui.heading("FDM Cost Calculator");
-
-                // Currency switcher button
-                if ui
-                    .button(format!(
-                        "Switch to {}",
-                        match self.logic.currency {
-                            Currency::USD => "€", // EUR symbol
-                            Currency::EUR => "£", // GBP symbol
-                            Currency::GBP => "$", // USD symbol
+            egui::ScrollArea::vertical()
+                .id_salt("main_scroll")
+                .show(ui, |ui| {
+                    // App heading, display currency and number format
+                    ui.horizontal(|ui| {
+                        ui.heading("FDM Cost Calculator");
+
+                        let mut currency = self.logic.currency;
+                        egui::ComboBox::from_id_salt("display_currency")
+                            .selected_text(currency.code())
+                            .show_ui(ui, |ui| {
+                                for option in Currency::all() {
+                                    ui.selectable_value(
+                                        &mut currency,
+                                        option,
+                                        format!("{} – {}", option.code(), option.name()),
+                                    );
+                                }
+                            });
+                        if currency != self.logic.currency {
+                            self.logic.set_currency(currency);
                         }
-                    ))
-                    .clicked()
-                {
-                    self.logic.switch_currency();
-                }
 
-                // Help button and logo
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if let Some(logo) = &self.logo {
-                        ui.image((logo.id(), egui::vec2(64.0, 64.0)));
-                    }
+                        egui::ComboBox::from_id_salt("number_locale")
+                            .selected_text(self.logic.locale.name)
+                            .show_ui(ui, |ui| {
+                                for locale in LOCALES {
+                                    ui.selectable_value(&mut self.logic.locale, locale, locale.name);
+                                }
+                            });
 
-                    if ui.selectable_label(self.show_help, egui::RichText::new("?").size(20.0))
-                        .clicked()
-                    {
-                        self.show_help = true;
-                    }
-                });
-            });
+                        // Help button and logo
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if let Some(logo) = &self.logo {
+                                ui.image((logo.id(), egui::vec2(64.0, 64.0)));
+                            }
 
-            ui.separator();
+                            if ui.selectable_label(self.show_help, egui::RichText::new("?").size(20.0))
+                                .clicked()
+                            {
+                                self.show_help = true;
+                            }
 
-            // Print Type Toggle
-            ui.horizontal(|ui| {
-                ui.label("Print Type:");
-                if ui.selectable_label(!self.is_multi_color, "Single Color").clicked() {
-                    self.is_multi_color = false;
-                    self.logic.update_filament_count(1); // Reset to one filament
-                }
-                if ui.selectable_label(self.is_multi_color, "Multi-Color/Material").clicked() {
-                    self.is_multi_color = true;
-                    if self.logic.filaments.len() < 2 {
-                        self.logic.update_filament_count(2); // Start with two filaments
-                    }
-                }
-            });
+                            if ui.selectable_label(self.show_settings, egui::RichText::new("⚙").size(20.0))
+                                .clicked()
+                            {
+                                self.show_settings = true;
+                            }
+                        });
+                    });
 
-            ui.separator();
+                    ui.separator();
 
-            // Filament Data Section
-            ui.heading("Filament Data");
-            egui::ScrollArea::vertical()
-                .max_height(200.0)
-                .show(ui, |ui| {
-                    ui.set_max_width(600.0);
-                    Grid::new("filament_grid")
-                        .num_columns(4)
-                        .spacing([20.0, 10.0])
+                    // Print Type Toggle
+                    ui.horizontal(|ui| {
+                        ui.label("Print Type:");
+                        if ui.selectable_label(!self.is_multi_color, "Single Color").clicked() {
+                            self.is_multi_color = false;
+                            self.logic.job.update_filament_count(1); // Reset to one filament
+                        }
+                        if ui.selectable_label(self.is_multi_color, "Multi-Color/Material").clicked() {
+                            self.is_multi_color = true;
+                            if self.logic.job.filaments.len() < 2 {
+                                self.logic.job.update_filament_count(2); // Start with two filaments
+                            }
+                        }
+                    });
+
+                    ui.separator();
+
+                    // Filament Data Section
+                    ui.heading("Filament Data");
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
                         .show(ui, |ui| {
-                            let mut remove_index = None; // Track the index of the filament to remove
-                            for (i, filament) in self.logic.filaments.iter_mut().enumerate() {
-                                ui.vertical(|ui| {
-                                    ui.group(|ui| {
-                                        ui.label(format!("Filament #{}", i + 1));
-
-                                        // Brand selection with constant "Custom" option
-                                        let mut brands: Vec<_> = self.logic.filament_prices.keys().cloned().collect();
-                                        brands.insert(0, "Custom"); // Ensure "Custom" is always an option
-                                        egui::ComboBox::new(format!("brand_selector_{}", i), "Select Brand")
-                                            .selected_text(filament.brand.clone())
-                                            .show_ui(ui, |ui| {
-                                                for brand in &brands {
-                                                    if ui.selectable_label(&filament.brand == brand, *brand).clicked() {
-                                                        filament.brand = brand.to_string();
-                                                        if *brand == "Custom" {
-                                                            filament.material = "Custom".to_string();
-                                                            filament.price_per_roll = 0.0;
-                                                            filament.roll_weight = 1000.0; // Default 1kg
+                            ui.set_max_width(600.0);
+                            Grid::new("filament_grid")
+                                .num_columns(4)
+                                .spacing([20.0, 10.0])
+                                .show(ui, |ui| {
+                                    let mut remove_index = None; // Track the index of the filament to remove
+                                    for (i, filament) in self.logic.job.filaments.iter_mut().enumerate() {
+                                        ui.vertical(|ui| {
+                                            ui.group(|ui| {
+                                                ui.label(format!("Filament #{}", i + 1));
+
+                                                // Brand selection with constant "Custom" option
+                                                let mut brands: Vec<_> = self.logic.filament_prices.keys().cloned().collect();
+                                                brands.insert(0, "Custom"); // Ensure "Custom" is always an option
+                                                egui::ComboBox::new(format!("brand_selector_{}", i), "Select Brand")
+                                                    .selected_text(filament.brand.clone())
+                                                    .show_ui(ui, |ui| {
+                                                        for brand in &brands {
+                                                            if ui.selectable_label(&filament.brand == brand, *brand).clicked() {
+                                                                filament.brand = brand.to_string();
+                                                                if *brand == "Custom" {
+                                                                    filament.set_material("Custom");
+                                                                    filament.price_per_roll = Money::zero(filament.price_per_roll.currency());
+                                                                    filament.roll_weight = 1000.0; // Default 1kg
+                                                                }
+                                                            }
                                                         }
+                                                    });
+
+                                                // Material selection
+                                                if filament.brand != "Custom" {
+                                                    if let Some(materials) = self.logic.filament_prices.get(filament.brand.as_str()) {
+                                                        let material_keys: Vec<_> = materials.keys().cloned().collect();
+                                                        egui::ComboBox::new(format!("material_selector_{}", i), "Select Material")
+                                                            .selected_text(filament.material.clone())
+                                                            .show_ui(ui, |ui| {
+                                                                for material in &material_keys {
+                                                                    if ui
+                                                                        .selectable_label(&filament.material == material, *material)
+                                                                        .clicked()
+                                                                    {
+                                                                        filament.set_material(material);
+                                                                        if let Some(price) = materials.get(material) {
+                                                                            // Scale price dynamically based on roll weight
+                                                                            filament.price_per_roll = price.scale(filament.roll_weight / 1000.0);
+                                                                            if filament.roll_weight == 0.0 {
+                                                                                filament.roll_weight = 1000.0; // Default roll weight to 1kg if unset
+                                                                            }
+                                                                        }                                                                
+                                                                    }
+                                                                }
+                                                            });
                                                     }
                                                 }
-                                            });
 
-                                        // Material selection
-                                        if filament.brand != "Custom" {
-                                            if let Some(materials) = self.logic.filament_prices.get(filament.brand.as_str()) {
-                                                let material_keys: Vec<_> = materials.keys().cloned().collect();
-                                                egui::ComboBox::new(format!("material_selector_{}", i), "Select Material")
-                                                    .selected_text(filament.material.clone())
+                                                // Additive category (CF, GF, metal-fill, ...)
+                                                egui::ComboBox::new(format!("additive_selector_{}", i), "Additive")
+                                                    .selected_text(filament.additive.label())
                                                     .show_ui(ui, |ui| {
-                                                        for material in &material_keys {
+                                                        for additive in Additive::ALL {
+                                                            ui.selectable_value(&mut filament.additive, additive, additive.label());
+                                                        }
+                                                    });
+
+                                                // Roll Weight
+                                                ui.horizontal(|ui| {
+                                                    ui.label(format!("Roll Weight ({}):", units.spool_weight.label()));
+                                                    weight_input(ui, &mut filament.roll_weight, units.spool_weight, locale);
+                                                });
+
+                                                // Diameter and density, used to convert length and volume to weight
+                                                ui.horizontal(|ui| {
+                                                    ui.label("Diameter:");
+                                                    for diameter in FILAMENT_DIAMETERS {
+                                                        ui.selectable_value(
+                                                            &mut filament.diameter,
+                                                            diameter,
+                                                            format!("{} mm", locale.format_input(diameter as f64, 2..=2)),
+                                                        );
+                                                    }
+                                                });
+                                                ui.horizontal(|ui| {
+                                                    ui.label("Density (g/cm³):");
+                                                    ui.add(drag_value(&mut filament.density, locale).speed(0.01).range(0.1..=10.0));
+                                                });
+
+                                                // Filament Used, entered as weight, length or volume
+                                                ui.horizontal(|ui| {
+                                                    ui.label("Filament Used per Plate:");
+                                                    egui::ComboBox::from_id_salt(format!("usage_input_{}", i))
+                                                        .selected_text(filament.usage_input.label())
+                                                        .show_ui(ui, |ui| {
+                                                            for quantity in FilamentQuantity::ALL {
+                                                                ui.selectable_value(&mut filament.usage_input, quantity, quantity.label());
+                                                            }
+                                                        });
+                                                });
+                                                ui.horizontal(|ui| {
+                                                    let weight_unit = units.filament_weight;
+                                                    match filament.usage_input {
+                                                        FilamentQuantity::Weight => {
+                                                            weight_input(ui, &mut filament.weight, weight_unit, locale);
+                                                            ui.label(weight_unit.label());
+                                                        }
+                                                        FilamentQuantity::Length => {
+                                                            let unit = units.filament_length;
+                                                            let mut length = unit.convert(filament.length_mm(filament.weight));
                                                             if ui
-                                                                .selectable_label(&filament.material == material, *material)
-                                                                .clicked()
+                                                                .add(drag_value(&mut length, locale).speed(unit.step()).range(0.0..=f32::MAX))
+                                                                .changed()
                                                             {
-                                                                filament.material = material.to_string();
-                                                                if let Some(price) = materials.get(material) {
-                                                                    // Scale price dynamically based on roll weight
-                                                                    filament.price_per_roll = *price * (filament.roll_weight / 1000.0);
-                                                                    if filament.roll_weight == 0.0 {
-                                                                        filament.roll_weight = 1000.0; // Default roll weight to 1kg if unset
-                                                                    }
-                                                                }                                                                
+                                                                filament.weight = unit.millimeters_of(length) * filament.grams_per_mm();
                                                             }
+                                                            ui.label(unit.label());
                                                         }
-                                                    });
-                                            }
-                                        }
-
-                                        ui.checkbox(&mut filament.is_carbon_based, "Carbon-Based");
+                                                        FilamentQuantity::Volume => {
+                                                            let mut volume = filament.volume_cm3(filament.weight);
+                                                            if ui
+                                                                .add(drag_value(&mut volume, locale).speed(0.1).range(0.0..=f32::MAX))
+                                                                .changed()
+                                                            {
+                                                                filament.weight = volume * filament.density;
+                                                            }
+                                                            ui.label("cm³");
+                                                        }
+                                                    }
+                                                    if filament.usage_input != FilamentQuantity::Weight {
+                                                        ui.label(format!(
+                                                            "= {} {}",
+                                                            locale.format_input(weight_unit.convert(filament.weight) as f64, 1..=2),
+                                                            weight_unit.label()
+                                                        ));
+                                                    }
+                                                });
 
-                                        // Roll Weight
-                                        ui.horizontal(|ui| {
-                                            ui.label("Roll Weight (grams):");
-                                            ui.add(egui::DragValue::new(&mut filament.roll_weight).speed(1.0));
-                                        });
+                                                // Purge assigned to this filament
+                                                ui.horizontal(|ui| {
+                                                    ui.label(format!("Purge/Waste per Plate ({}):", units.filament_weight.label()));
+                                                    weight_input(ui, &mut filament.purge_weight, units.filament_weight, locale);
+                                                });
 
-                                        // Filament Used
-                                        ui.horizontal(|ui| {
-                                            ui.label("Filament Used for Print (grams):");
-                                            ui.add(egui::DragValue::new(&mut filament.weight).speed(1.0));
-                                        });
+                                                // Price per Roll (formatted)
+                                                ui.horizontal(|ui| {
+                                                    ui.label("Price per roll:");
+                                                    money_input(ui, &mut filament.price_per_roll, locale, 0.1);
+                                                    let mut currency = filament.price_per_roll.currency();
+                                                    egui::ComboBox::from_id_salt(format!("price_currency_{}", i))
+                                                        .width(60.0)
+                                                        .selected_text(currency.code())
+                                                        .show_ui(ui, |ui| {
+                                                            for option in Currency::all() {
+                                                                ui.selectable_value(&mut currency, option, option.code());
+                                                            }
+                                                        });
+                                                    if currency != filament.price_per_roll.currency() {
+                                                        // Keep the number as typed, now read in the chosen currency
+                                                        filament.price_per_roll =
+                                                            Money::from_f64(filament.price_per_roll.to_f64(), currency);
+                                                    }
+                                                });
 
-                                        // Price per Roll (formatted)
-                                        ui.horizontal(|ui| {
-                                            ui.label("Price (€ per roll):");
-                                            ui.add(
-                                                egui::DragValue::new(&mut filament.price_per_roll)
-                                                    .speed(0.1)
-                                                    .min_decimals(2)
-                                                    .max_decimals(2),
-                                            );
+                                                if self.is_multi_color
+                                                    && i > 0
+                                                    && ui.button(egui::RichText::new("🗑️").size(18.0)).clicked()
+                                                {
+                                                    remove_index = Some(i);
+                                                }
+                                            });
                                         });
 
-                                        if self.is_multi_color && i > 0 {
-                                            if ui.button(egui::RichText::new("🗑️").size(18.0)).clicked() {
-                                                remove_index = Some(i);
-                                            }
+                                        if (i + 1) % 3 == 0 {
+                                            ui.end_row();
                                         }
-                                    });
+                                    }
+
+                                    if let Some(index) = remove_index {
+                                        self.logic.job.remove_filament(index);
+                                    }
                                 });
+                        });
 
-                                if (i + 1) % 3 == 0 {
-                                    ui.end_row();
-                                }
+                    if self.is_multi_color && self.logic.job.filaments.len() < 16 {
+                        ui.horizontal(|ui| {
+                            if ui.button("+ Add Filament").clicked() {
+                                self.logic.job.add_filament();
                             }
+                        });
+                    }
+
+                    if self.is_multi_color {
+                        ui.collapsing("Flushing Volumes", |ui| self.flush_plan_editor(ui));
+                    }
+
+                    ui.separator();
+
+                    // Batch Section
+                    ui.heading("Batch");
+                    ui.horizontal(|ui| {
+                        ui.label("Quantity (parts):");
+                        ui.add(drag_value(&mut self.logic.job.quantity, locale).range(1..=MAX_QUANTITY));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Parts per plate:");
+                        ui.add(drag_value(&mut self.logic.job.parts_per_plate, locale).range(1..=1000));
+                    });
+                    ui.label(format!("Plates needed: {}", self.logic.job.plate_count()));
+
+                    ui.separator();
+
+                    // Labor Section
+                    ui.heading("Labor");
+                    self.labor_editor(ui);
 
-                            if let Some(index) = remove_index {
-                                self.logic.remove_filament(index);
+                    ui.separator();
+
+                    // Printer Data Section
+                    ui.heading("Printer Data");
+                    ui.horizontal(|ui| {
+                        ui.label("Printer:");
+                        let selected = self
+                            .logic
+                            .printer_for(&self.logic.job)
+                            .map(|printer| printer.name.clone())
+                            .unwrap_or_default();
+                        egui::ComboBox::from_id_salt("printer_selector")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for (i, printer) in self.logic.printers.iter().enumerate() {
+                                    ui.selectable_value(&mut self.logic.job.printer, i, &printer.name);
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "Unassigned Purge/Waste per Plate ({}, split by usage):",
+                            units.filament_weight.label()
+                        ));
+                        weight_input(ui, &mut self.logic.job.purge_waste_weight, units.filament_weight, locale);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Base electricity rate ({}/kWh):", self.logic.exchange_rates.base.code()));
+                        ui.add(drag_value(&mut self.logic.tariff.base_rate, locale).speed(0.01));
+                        if !self.logic.tariff.bands.is_empty() || !self.logic.tariff.tiers.is_empty() {
+                            ui.label("(time bands and tiers in ⚙ Settings)");
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Print time per plate (hours):");
+                        ui.add(drag_value(&mut self.logic.job.print_time, locale).speed(0.1));
+                    });
+                    ui.horizontal(|ui| {
+                        // Planned start, edited as day of week and hour of day
+                        let mut day = (self.logic.job.start_time / 24.0) as usize % 7;
+                        let mut hour = self.logic.job.start_time % 24.0;
+                        ui.label("Planned start:");
+                        egui::ComboBox::from_id_salt("start_day_selector")
+                            .selected_text(DAY_NAMES[day])
+                            .show_ui(ui, |ui| {
+                                for (i, name) in DAY_NAMES.iter().enumerate() {
+                                    ui.selectable_value(&mut day, i, *name);
+                                }
+                            });
+                        ui.add(drag_value(&mut hour, locale).speed(0.25).range(0.0..=23.75).suffix(" h"));
+                        self.logic.job.start_time = day as f32 * 24.0 + hour;
+                    });
+                    ui.horizontal(|ui| {
+                        let mut override_rate = self.logic.job.failure_rate_override.is_some();
+                        if ui.checkbox(&mut override_rate, "Override failure rate").changed() {
+                            // Start the override from the material and printer default
+                            let default_rate = self.logic.base_failure_rate(&self.logic.job);
+                            self.logic.job.failure_rate_override = override_rate.then_some(default_rate);
+                        }
+                        if let Some(rate) = &mut self.logic.job.failure_rate_override {
+                            ui.add(drag_value(rate, locale).speed(0.1).range(0.0..=90.0).suffix("% per 10 h"));
+                        } else {
+                            ui.label(format!(
+                                "{:.1}% per 10 h (from material and printer)",
+                                self.logic.base_failure_rate(&self.logic.job)
+                            ));
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Hourly Charge ({}/hour):", self.logic.exchange_rates.base.symbol()));
+                        money_input(ui, &mut self.logic.hourly_charge, locale, 0.1);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Shipping cost:");
+                        money_input(ui, &mut self.logic.job.shipping_cost, locale, 0.1);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Profit:");
+                        ui.selectable_value(&mut self.logic.pricing_mode, PricingMode::Markup, "Markup");
+                        ui.selectable_value(&mut self.logic.pricing_mode, PricingMode::Margin, "Margin");
+                    });
+                    ui.horizontal(|ui| match self.logic.pricing_mode {
+                        PricingMode::Markup => {
+                            ui.label("Markup percentage (of cost):");
+                            ui.add(drag_value(&mut self.logic.markup_percentage, locale).speed(1.0));
+                        }
+                        PricingMode::Margin => {
+                            ui.label("Margin percentage (of price):");
+                            ui.add(
+                                drag_value(&mut self.logic.margin_percentage, locale)
+                                    .speed(1.0)
+                                    .range(0.0..=99.0),
+                            );
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Setup fee per job:");
+                        money_input(ui, &mut self.logic.setup_fee, locale, 0.1);
+                        ui.label("Minimum order value:");
+                        money_input(ui, &mut self.logic.minimum_order_value, locale, 0.1);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Sales channel:");
+                        let selected = self
+                            .logic
+                            .sales_channel_for(&self.logic.job)
+                            .map(|channel| channel.name.clone())
+                            .unwrap_or_default();
+                        egui::ComboBox::from_id_salt("sales_channel_selector")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for (i, channel) in self.logic.sales_channels.iter().enumerate() {
+                                    ui.selectable_value(&mut self.logic.job.sales_channel, i, &channel.name);
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Payment method:");
+                        let selected = self
+                            .logic
+                            .payment_method_for(&self.logic.job)
+                            .map(|payment| payment.name.clone())
+                            .unwrap_or_default();
+                        egui::ComboBox::from_id_salt("payment_method_selector")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for (i, payment) in self.logic.payment_methods.iter().enumerate() {
+                                    ui.selectable_value(&mut self.logic.job.payment_method, i, &payment.name);
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        let rounding = &mut self.logic.rounding;
+                        ui.label("Rounding:");
+                        egui::ComboBox::from_id_salt("rounding_selector")
+                            .selected_text(rounding.strategy.label())
+                            .show_ui(ui, |ui| {
+                                for strategy in RoundingStrategy::ALL {
+                                    ui.selectable_value(&mut rounding.strategy, strategy, strategy.label());
+                                }
+                            });
+                        match rounding.strategy {
+                            RoundingStrategy::Nearest | RoundingStrategy::Up => {
+                                ui.label("Step:");
+                                for step in [0.05, 0.10, 0.50, 1.00] {
+                                    ui.selectable_value(&mut rounding.step, step, locale.format_input(step as f64, 2..=2));
+                                }
+                                ui.add(drag_value(&mut rounding.step, locale).speed(0.01).range(0.01..=100.0));
                             }
-                        });
-                });
+                            RoundingStrategy::Charm => {
+                                // Currencies without decimals end in 99 or 95 instead
+                                let prefix = if self.logic.currency.minor_units() == 0 {
+                                    "…".to_string()
+                                } else {
+                                    locale.decimal_separator.to_string()
+                                };
+                                ui.label("Ending:");
+                                ui.selectable_value(&mut rounding.charm_ending, 0.99, format!("{}99", prefix));
+                                ui.selectable_value(&mut rounding.charm_ending, 0.95, format!("{}95", prefix));
+                            }
+                            RoundingStrategy::CurrencyStep => {
+                                ui.label(format!(
+                                    "Step: {}",
+                                    locale.format_money(Money::from_minor_units(
+                                        self.logic.currency.cash_step(),
+                                        self.logic.currency
+                                    ))
+                                ));
+                            }
+                            RoundingStrategy::None => {}
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Pricing:");
+                        ui.selectable_value(&mut self.logic.prices_include_tax, false, "Tax-exclusive (B2B)");
+                        ui.selectable_value(&mut self.logic.prices_include_tax, true, "Tax-inclusive (consumer)");
+                    });
+
+                    ui.separator();
+
+                    // Post Processing Section
+                    ui.heading("Post Processing");
+                    self.post_processing_editor(ui);
 
-            if self.is_multi_color && self.logic.filaments.len() < 16 {
-                ui.horizontal(|ui| {
-                    if ui.button("+ Add Filament").clicked() {
-                        self.logic.add_filament();
+                    ui.separator();
+
+                    // Totals Section
+                    ui.heading("Totals and Suggested Pricing");
+                    if ui.button("Calculate").clicked() {
+                        self.logic.calculate_costs();
                     }
-                });
-            }
 
-            ui.separator();
+                    if self.logic.exchange_rates.rate(self.logic.currency).is_none() {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            format!(
+                                "No exchange rate from {} to {}: amounts are not converted.",
+                                self.logic.exchange_rates.base.code(),
+                                self.logic.currency.code()
+                            ),
+                        );
+                    }
 
-            // Printer Data Section
-            ui.heading("Printer Data");
-            ui.horizontal(|ui| {
-                ui.label("Purge/Waste Filament (grams):");
-                ui.add(egui::DragValue::new(&mut self.logic.purge_waste_weight).speed(1.0));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Electricity rate (EUR/kWh):");
-                ui.add(egui::DragValue::new(&mut self.logic.electricity_rate).speed(0.01));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Printer wattage (W):");
-                ui.add(egui::DragValue::new(&mut self.logic.printer_wattage).speed(1.0));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Print time (hours):");
-                ui.add(egui::DragValue::new(&mut self.logic.print_time).speed(0.1));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Hourly Charge (€/hour):");
-                ui.add(egui::DragValue::new(&mut self.logic.hourly_charge).speed(0.1));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Shipping cost:");
-                ui.add(egui::DragValue::new(&mut self.logic.shipping_cost).speed(0.1));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Markup percentage:");
-                ui.add(egui::DragValue::new(&mut self.logic.markup_percentage).speed(1.0));
-            });
+                    // Cost breakdown, one row per component
+                    let breakdown = &self.logic.breakdown;
+                    Grid::new("breakdown_grid")
+                        .num_columns(2)
+                        .spacing([40.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for line in &breakdown.lines {
+                                ui.label(&line.label);
+                                ui.label(locale.format_money(line.amount));
+                                ui.end_row();
+                            }
+                        });
 
-            ui.separator();
+                    // Pricing pipeline, one row per stage with the running total after it
+                    ui.collapsing("Pricing Pipeline", |ui| {
+                        Grid::new("pricing_pipeline_grid")
+                            .num_columns(4)
+                            .spacing([40.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong("Stage");
+                                ui.strong("Amount");
+                                ui.strong("Markup applies");
+                                ui.strong("Running total");
+                                ui.end_row();
+                                let mut running_total = Money::zero(breakdown.currency);
+                                for stage in PricingStage::ALL {
+                                    let amount = breakdown.total_for_stage(stage);
+                                    running_total += amount;
+                                    ui.label(stage.label());
+                                    ui.label(locale.format_money(amount));
+                                    if stage == PricingStage::Markup {
+                                        ui.label(format!("on {}", locale.format_money(breakdown.markup_base)));
+                                    } else if stage.is_cost() {
+                                        ui.label(if self.logic.markup_applies(stage) { "yes" } else { "no" });
+                                    } else {
+                                        ui.label("");
+                                    }
+                                    ui.label(locale.format_money(running_total));
+                                    ui.end_row();
+                                }
+                            });
+                    });
 
-            // Post Processing Section
-            ui.heading("Post Processing Data");
-            ui.horizontal(|ui| {
-                ui.label("Post-Processing Hours:");
-                ui.add(egui::DragValue::new(&mut self.logic.post_processing_hours).speed(0.1));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Hourly Rate (€/hour):");
-                ui.add(egui::DragValue::new(&mut self.logic.post_processing_rate).speed(0.1));
-            });
+                    ui.label(format!("Total cost: {}", locale.format_money(breakdown.total_cost)));
+                    ui.label(format!(
+                        "Suggested price (with markup): {}",
+                        locale.format_money(breakdown.suggested_price)
+                    ));
+                    if !breakdown.tax.is_zero() {
+                        ui.label(format!("Tax: {}", locale.format_money(breakdown.tax)));
+                        ui.label(format!(
+                            "Suggested price (incl. tax): {}",
+                            locale.format_money(breakdown.gross_price)
+                        ));
+                    }
+                    if let Some((start, cost)) = self.logic.cheapest_start {
+                        let current = breakdown.total_for(CostKind::Electricity);
+                        if cost < current {
+                            ui.label(format!(
+                                "Cheapest start in the next 24 hours: {} (electricity {}, saves {})",
+                                format_week_time(start),
+                                locale.format_money(cost),
+                                locale.format_money(current - cost)
+                            ));
+                        }
+                    }
+                    if !breakdown.minimum_order_adjustment.is_zero() {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            format!(
+                                "Minimum order value applied: price raised by {}",
+                                locale.format_money(breakdown.minimum_order_adjustment)
+                            ),
+                        );
+                    }
+                    let quoted_price = breakdown.quoted_price();
+                    if quoted_price != breakdown.unrounded_price {
+                        let adjustment = quoted_price - breakdown.unrounded_price;
+                        ui.label(format!(
+                            "Unrounded price: {} (rounding adjustment {}{})",
+                            locale.format_money(breakdown.unrounded_price),
+                            if adjustment > Money::zero(adjustment.currency()) { "+" } else { "" },
+                            locale.format_money(adjustment)
+                        ));
+                    }
+                    ui.strong(format!(
+                        "Quoted price ({}): {}",
+                        if breakdown.tax_inclusive { "incl. tax" } else { "excl. tax" },
+                        locale.format_money(quoted_price)
+                    ));
+                    if breakdown.quantity > 1 {
+                        ui.label(format!(
+                            "Per unit ({} parts): cost {}, price {} ({})",
+                            breakdown.quantity,
+                            locale.format_money(breakdown.unit_cost()),
+                            locale.format_money(breakdown.unit_price()),
+                            if breakdown.tax_inclusive { "incl. tax" } else { "excl. tax" }
+                        ));
+                    }
 
-            ui.separator();
+                    ui.label(format!(
+                        "Machine time: {:.2} h, attended time: {:.2} h",
+                        breakdown.machine_hours, breakdown.attended_hours
+                    ));
 
-            // Totals Section
-            ui.heading("Totals and Suggested Pricing");
-            if ui.button("Calculate").clicked() {
-                self.logic.calculate_costs();
-            }
+                    // Reverse pricing from a target or competitor price
+                    ui.collapsing("Reverse Pricing", |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Target or competitor price:");
+                            money_input(ui, &mut self.logic.target_price, locale, 0.1);
+                        });
+                        if let Some(quote) = &self.logic.reverse_quote {
+                            ui.label(format!("At {}:", locale.format_money(quote.target_price)));
+                            ui.label(format!(
+                                "Net revenue after tax and fees: {}",
+                                locale.format_money(quote.net_revenue)
+                            ));
+                            ui.label(format!("Profit: {}", locale.format_money(quote.profit)));
+                            ui.label(format!("Markup: {:.1}%", quote.markup_percentage));
+                            ui.label(format!("Margin: {:.1}%", quote.margin_percentage));
+                            ui.label(format!(
+                                "Profit per machine hour: {}",
+                                locale.format_money(quote.profit_per_machine_hour)
+                            ));
+                        } else {
+                            ui.label("Enter a price and click 'Calculate'.");
+                        }
+                    });
 
-            ui.label(format!(
-                "Total cost: {:.2} {}",
-                self.logic.total_cost,
-                self.logic.currency_symbol()
-            ));
-            ui.label(format!(
-                "Wear and tear cost: {:.2} {}",
-                self.logic.wear_and_tear_cost,
-                self.logic.currency_symbol()
-            ));
-            ui.label(format!(
-                "Suggested price (with markup): {:.2} {}",
-                self.logic.suggested_price,
-                self.logic.currency_symbol()
-            ));
-            ui.label(format!(
-                "Suggested price (with post-processing): {:.2} {}",
-                self.logic.suggested_price_with_post_processing,
-                self.logic.currency_symbol()
-            ));
+                    // Price breaks for common order quantities
+                    if !self.logic.price_breaks.is_empty() {
+                        ui.collapsing("Price Breaks", |ui| {
+                            Grid::new("price_break_grid")
+                                .num_columns(4)
+                                .spacing([30.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label("Quantity");
+                                    ui.label("Unit cost");
+                                    ui.label(if breakdown.tax_inclusive {
+                                        "Unit price (incl. tax)"
+                                    } else {
+                                        "Unit price (excl. tax)"
+                                    });
+                                    ui.label("Margin");
+                                    ui.end_row();
+                                    for row in &self.logic.price_breaks {
+                                        ui.label(row.quantity.to_string());
+                                        ui.label(locale.format_money(row.unit_cost));
+                                        ui.label(locale.format_money(row.unit_price));
+                                        ui.label(format!("{:.1}%", row.margin_percentage));
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                });
         });
 
+        // Settings Window
+        if self.show_settings {
+            let mut open = true;
+            egui::Window::new("Settings")
+                .open(&mut open)
+                .collapsible(false)
+                .vscroll(true)
+                .show(ctx, |ui| {
+                    ui.collapsing("Units", |ui| self.unit_settings(ui));
+                    ui.collapsing("Exchange Rates", |ui| self.exchange_rate_settings(ui));
+                    ui.collapsing("Printer Profiles", |ui| self.printer_settings(ui));
+                    ui.collapsing("Labor Roles", |ui| self.labor_role_settings(ui));
+                    ui.collapsing("Pricing Pipeline", |ui| self.pricing_pipeline_settings(ui));
+                    ui.collapsing("Post-Processing Catalog", |ui| self.post_processing_settings(ui));
+                    ui.collapsing("Filament Additives", |ui| self.additive_settings(ui));
+                    ui.collapsing("Quantity Discounts", |ui| self.discount_settings(ui));
+                    ui.collapsing("Electricity Tariff", |ui| self.tariff_settings(ui));
+                    ui.collapsing("Tax Rates", |ui| self.tax_settings(ui));
+                    ui.collapsing("Sales Channels", |ui| self.sales_channel_settings(ui));
+                    ui.collapsing("Payment Methods", |ui| self.payment_method_settings(ui));
+                });
+            self.show_settings = open;
+        }
+
         // Help Dialog
         if self.show_help {
             egui::Window::new("Help")
@@ -278,29 +725,49 @@ impl eframe::App for CalculatorUI {
                         ui.label("• **Specifying Filament Details:** For each filament:");
                         ui.indent("filament_info", |ui| {
                             ui.label("   - Select the brand and material.");
-                            ui.label("   - Specify weight in grams.");
+                            ui.label("   - Specify usage per plate as a weight, or as a length or volume that is converted to weight.");
+                            ui.label("   - Pick the diameter (1.75 or 2.85 mm) and check the density, which defaults from the material.");
+                            ui.label("   - Optionally assign purge/waste weight to the filament being flushed.");
                             ui.label("   - Input cost per roll and roll weight.");
-                            ui.label("   - Check 'Carbon-Based' if applicable.");
+                            ui.label("   - Choose an additive (carbon fiber, glass fiber, metal-fill, glow, wood) if applicable.");
                         });
                         ui.label("• **Inputting Additional Costs:** Provide:");
                         ui.indent("additional_costs", |ui| {
-                            ui.label("   - Purge/Waste Filament weight.");
-                            ui.label("   - Electricity rate (EUR/kWh).");
-                            ui.label("   - Printer wattage (W).");
-                            ui.label("   - Print time in hours.");
+                            ui.label("   - Unassigned Purge/Waste weight, split across filaments by usage.");
+                            ui.label("   - In Multi-Color mode, optionally a flushing volume matrix and tool changes to estimate purge.");
+                            ui.label(format!(
+                                "   - Electricity tariff in {}/kWh and planned start time: a base rate, plus optional time-of-use bands (e.g. night rates on weekdays) and consumption tiers set in ⚙ Settings. Energy is priced at the band or tier in effect while the printer draws it.",
+                                self.logic.exchange_rates.base.code()
+                            ));
+                            ui.label("   - Printer profile used for the print; its power draw follows the material's bed and nozzle temperatures, warm-up per plate and standby between plates.");
+                            ui.label("   - Print time per plate in hours.");
+                            ui.label("   - Batch quantity and parts per plate.");
+                            ui.label("   - Labor phases (prep, plate removal, support removal, QC, packing) with minutes per job, plate or part and the role doing them.");
+                            ui.label("   - Failure rate (defaults from material and printer, scaled by print time).");
                             ui.label("   - Hourly charge for printing.");
                             ui.label("   - Shipping cost.");
-                            ui.label("   - Markup percentage for profit.");
-                            ui.label("   - Post-Processing Hours and Hourly Rate.");
+                            ui.label("   - Markup (percentage of cost) or margin (percentage of price) for profit.");
+                            ui.label("   - Sales channel and payment method; their fees are built into the list price.");
+                            ui.label("   - Setup fee per job and minimum order value (applied before fees and tax).");
+                            ui.label("   - Rounding of the quoted price (nearest step, round up, charm pricing, or the currency's smallest cash step such as 0.05 CHF or 1 SEK).");
+                            ui.label("   - Tax-exclusive (B2B) or tax-inclusive (consumer) pricing.");
+                            ui.label("   - Post-processing operations from the catalog (sanding, priming, painting, vapor smoothing, annealing, heat-set inserts), repeated per part as needed.");
                         });
                         ui.label("• **Results:** After clicking 'Calculate', the app will display:");
                         ui.indent("results_info", |ui| {
+                            ui.label("   - A line-by-line breakdown of every cost component, each line rounded to the currency's minor unit so the lines add up exactly to the totals.");
                             ui.label("   - Total cost of the print.");
                             ui.label("   - Suggested selling price with markup.");
                             ui.label("   - Wear and tear cost.");
-                            ui.label("   - Suggested price (with post-processing).");
+                            ui.label("   - The pricing pipeline: each stage (costs, markup, adjustments, fees, rounding, tax), whether markup applies to it and the running total.");
+                            ui.label("   - Cost and price per unit for batch jobs.");
+                            ui.label("   - Machine time and attended time of the job.");
+                            ui.label("   - The cheapest start time in the next 24 hours when it saves on electricity.");
+                            ui.label("   - Under 'Reverse Pricing', the markup, margin and profit per machine hour of a target price.");
+                            ui.label("   - A price-break table with unit cost, unit price and margin for common quantities.");
                         });
-                        ui.label("• **Switching Currency:** Use the currency button to switch between €, £, and $ for display purposes only.");
+                        ui.label("• **Settings:** Click ⚙ to choose metric or imperial units (or a unit per quantity: spool weight, filament weight and filament length), and to edit printer profiles (purchase price, lifetime, residual value, maintenance budget, power profile and consumable parts), labor roles and hourly rates, which cost stages the markup applies to, the post-processing catalog (time, role and consumable cost per part), additive surcharges, quantity discount tiers, electricity tariffs, tax rates, sales channel fees and payment method fees.");
+                        ui.label("• **Currency and Number Format:** Pick any active ISO 4217 currency (fund codes included, precious metals not) next to the heading to show results in it, and a number format to choose the decimal and thousands separators and where the currency symbol goes. Numbers can be typed with a decimal comma or point. Prices and rates are entered in the base currency (filament prices in their own currency) and are converted when the base currency changes, prices and fixed fees are kept as exact amounts in the currency's minor unit, and every result is converted with the exchange rates in ⚙ Settings, which can be edited or loaded from an ECB XML or CSV file.");
                     });
                     if ui.button("Close").clicked() {
                         self.show_help = false;
@@ -310,6 +777,844 @@ impl eframe::App for CalculatorUI {
     }
 }
 
+impl CalculatorUI {
+    /// Shows the editor for the electricity tariff's time bands and consumption tiers.
+    fn tariff_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let tariff = &mut self.logic.tariff;
+        ui.horizontal(|ui| {
+            ui.label("Base rate (per kWh):");
+            ui.add(drag_value(&mut tariff.base_rate, locale).speed(0.01));
+        });
+
+        ui.label("Time bands (override the base rate and tiers):");
+        let mut remove_band = None;
+        Grid::new("tariff_bands")
+            .num_columns(6)
+            .show(ui, |ui| {
+                ui.label("Name");
+                ui.label("Days");
+                ui.label("From (h)");
+                ui.label("To (h)");
+                ui.label("Rate (per kWh)");
+                ui.end_row();
+                for (i, band) in tariff.bands.iter_mut().enumerate() {
+                    ui.text_edit_singleline(&mut band.name);
+                    egui::ComboBox::from_id_salt(format!("band_days_{}", i))
+                        .selected_text(band.days.label())
+                        .show_ui(ui, |ui| {
+                            for rule in DayRule::ALL {
+                                ui.selectable_value(&mut band.days, rule, rule.label());
+                            }
+                        });
+                    ui.add(drag_value(&mut band.start_hour, locale).speed(0.25).range(0.0..=24.0));
+                    ui.add(drag_value(&mut band.end_hour, locale).speed(0.25).range(0.0..=24.0));
+                    ui.add(drag_value(&mut band.rate, locale).speed(0.01));
+                    if ui.button("🗑️").clicked() {
+                        remove_band = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_band {
+            tariff.bands.remove(index);
+        }
+        if ui.button("+ Add Time Band").clicked() {
+            tariff.bands.push(TimeBand {
+                name: "Night".to_string(),
+                days: DayRule::EveryDay,
+                start_hour: 22.0,
+                end_hour: 6.0,
+                rate: tariff.base_rate,
+            });
+        }
+
+        ui.separator();
+        ui.label("Consumption tiers (replace the base rate):");
+        let mut remove_tier = None;
+        Grid::new("tariff_tiers")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("Up to (kWh per period)");
+                ui.label("Rate (per kWh)");
+                ui.end_row();
+                for (i, tier) in tariff.tiers.iter_mut().enumerate() {
+                    ui.add(drag_value(&mut tier.up_to_kwh, locale).speed(1.0).range(0.0..=1_000_000.0));
+                    ui.add(drag_value(&mut tier.rate, locale).speed(0.01));
+                    if ui.button("🗑️").clicked() {
+                        remove_tier = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_tier {
+            tariff.tiers.remove(index);
+        }
+        if ui.button("+ Add Tier").clicked() {
+            let up_to_kwh = tariff.tiers.last().map_or(100.0, |tier| tier.up_to_kwh + 100.0);
+            tariff.tiers.push(KwhTier { up_to_kwh, rate: tariff.base_rate });
+        }
+        if !tariff.tiers.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label("kWh already used this billing period:");
+                ui.add(drag_value(&mut tariff.period_kwh_used, locale).speed(1.0).range(0.0..=1_000_000.0));
+            });
+        }
+    }
+
+    /// Shows the editor for the payment method fee profiles.
+    fn payment_method_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let mut remove_index = None;
+        let can_remove = self.logic.payment_methods.len() > 1;
+        Grid::new("payment_methods")
+            .num_columns(5)
+            .show(ui, |ui| {
+                ui.label("Method");
+                ui.label("Fee (%)");
+                ui.label("Fixed fee");
+                ui.label("Currency conversion (%)");
+                ui.end_row();
+                for (i, payment) in self.logic.payment_methods.iter_mut().enumerate() {
+                    ui.text_edit_singleline(&mut payment.name);
+                    ui.add(drag_value(&mut payment.fee_percentage, locale).speed(0.1).range(0.0..=99.0));
+                    money_input(ui, &mut payment.fixed_fee, locale, 0.01);
+                    ui.add(
+                        drag_value(&mut payment.currency_conversion_percentage, locale)
+                            .speed(0.1)
+                            .range(0.0..=99.0),
+                    );
+                    if can_remove && ui.button("🗑️").clicked() {
+                        remove_index = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_index {
+            self.logic.payment_methods.remove(index);
+            shift_selection(&mut self.logic.job.payment_method, index);
+        }
+        if ui.button("+ Add Payment Method").clicked() {
+            self.logic.payment_methods.push(PaymentMethod {
+                name: "New method".to_string(),
+                fee_percentage: 0.0,
+                fixed_fee: Money::zero(self.logic.exchange_rates.base),
+                currency_conversion_percentage: 0.0,
+            });
+        }
+    }
+
+    /// Shows the editor for the sales channel fee profiles.
+    fn sales_channel_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let mut remove_index = None;
+        let can_remove = self.logic.sales_channels.len() > 1;
+        Grid::new("sales_channels")
+            .num_columns(5)
+            .show(ui, |ui| {
+                ui.label("Channel");
+                ui.label("Fee (%)");
+                ui.label("Fixed fee");
+                ui.label("Shipping fee (%)");
+                ui.end_row();
+                for (i, channel) in self.logic.sales_channels.iter_mut().enumerate() {
+                    ui.text_edit_singleline(&mut channel.name);
+                    ui.add(drag_value(&mut channel.fee_percentage, locale).speed(0.1).range(0.0..=99.0));
+                    money_input(ui, &mut channel.fixed_fee, locale, 0.01);
+                    ui.add(
+                        drag_value(&mut channel.shipping_fee_percentage, locale)
+                            .speed(0.1)
+                            .range(0.0..=99.0),
+                    );
+                    if can_remove && ui.button("🗑️").clicked() {
+                        remove_index = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_index {
+            self.logic.sales_channels.remove(index);
+            shift_selection(&mut self.logic.job.sales_channel, index);
+        }
+        if ui.button("+ Add Channel").clicked() {
+            self.logic.sales_channels.push(SalesChannel {
+                name: "New channel".to_string(),
+                fee_percentage: 0.0,
+                fixed_fee: Money::zero(self.logic.exchange_rates.base),
+                shipping_fee_percentage: 0.0,
+            });
+        }
+    }
+
+    /// Shows the units that weights and lengths are entered in, with metric and imperial
+    /// presets.
+    fn unit_settings(&mut self, ui: &mut egui::Ui) {
+        let previous = self.logic.units;
+        let units = &mut self.logic.units;
+        ui.horizontal(|ui| {
+            ui.selectable_value(units, UnitSettings::METRIC, "Metric");
+            ui.selectable_value(units, UnitSettings::IMPERIAL, "Imperial");
+        });
+        Grid::new("unit_settings")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (label, unit) in [
+                    ("Spool weight", &mut units.spool_weight),
+                    ("Filament weight", &mut units.filament_weight),
+                ] {
+                    ui.label(label);
+                    egui::ComboBox::from_id_salt(label)
+                        .selected_text(unit.label())
+                        .show_ui(ui, |ui| {
+                            for option in WeightUnit::ALL {
+                                ui.selectable_value(unit, option, option.label());
+                            }
+                        });
+                    ui.end_row();
+                }
+                ui.label("Filament length");
+                egui::ComboBox::from_id_salt("filament_length_unit")
+                    .selected_text(units.filament_length.label())
+                    .show_ui(ui, |ui| {
+                        for option in LengthUnit::ALL {
+                            ui.selectable_value(&mut units.filament_length, option, option.label());
+                        }
+                    });
+                ui.end_row();
+            });
+        // Breakdown labels quote weights in the selected unit
+        if self.logic.units != previous && !self.logic.breakdown.lines.is_empty() {
+            self.logic.calculate_costs();
+        }
+    }
+
+    /// Shows the base currency and the exchange-rate table, which can be loaded from an
+    /// ECB-format XML or CSV file.
+    fn exchange_rate_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        ui.horizontal(|ui| {
+            ui.label("Base currency of prices and rates:");
+            let mut base = self.logic.exchange_rates.base;
+            egui::ComboBox::from_id_salt("base_currency")
+                .selected_text(base.code())
+                .show_ui(ui, |ui| {
+                    for currency in Currency::all() {
+                        ui.selectable_value(&mut base, currency, currency.code());
+                    }
+                });
+            if base != self.logic.exchange_rates.base {
+                self.exchange_rates_status = if self.logic.set_base_currency(base) {
+                    format!("Converted stored prices and rates to {}.", base.code())
+                } else {
+                    format!("Enter a rate for {} first.", base.code())
+                };
+            }
+        });
+        let rates = &mut self.logic.exchange_rates;
+        Grid::new("exchange_rates")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("Currency");
+                ui.label(format!("Units per 1 {}", rates.base.code()));
+                ui.end_row();
+                let mut currencies: Vec<Currency> = rates.rates.keys().copied().collect();
+                currencies.sort_by_key(|currency| currency.code());
+                for currency in currencies {
+                    ui.label(currency.code()).on_hover_text(currency.name());
+                    if let Some(rate) = rates.rates.get_mut(&currency) {
+                        ui.add(drag_value(rate, locale).speed(0.001).range(0.0..=f32::MAX).max_decimals(4));
+                    }
+                    if ui.button("🗑️").clicked() {
+                        rates.rates.remove(&currency);
+                    }
+                    ui.end_row();
+                }
+            });
+        egui::ComboBox::from_id_salt("add_exchange_rate")
+            .selected_text("Add currency")
+            .show_ui(ui, |ui| {
+                for currency in Currency::all() {
+                    if currency != rates.base
+                        && !rates.rates.contains_key(&currency)
+                        && ui.selectable_label(false, format!("{} – {}", currency.code(), currency.name())).clicked()
+                    {
+                        rates.rates.insert(currency, 1.0);
+                    }
+                }
+            });
+        ui.horizontal(|ui| {
+            ui.label("Load from file:");
+            ui.text_edit_singleline(&mut self.exchange_rates_path)
+                .on_hover_text("ECB eurofxref XML or CSV, or CSV rows of currency code and rate per euro");
+            if ui.button("Load").clicked() {
+                self.exchange_rates_status = match ExchangeRates::load_file(&self.exchange_rates_path) {
+                    Ok(mut loaded) => {
+                        if loaded.rebase(rates.base) {
+                            *rates = loaded;
+                            format!("Loaded {} exchange rates.", rates.rates.len())
+                        } else {
+                            format!("The file has no rate for {}.", rates.base.code())
+                        }
+                    }
+                    Err(err) => err,
+                };
+            }
+        });
+        if !self.exchange_rates_status.is_empty() {
+            ui.label(&self.exchange_rates_status);
+        }
+    }
+
+    /// Shows the editor for the labor roles and their hourly rates.
+    fn labor_role_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let mut remove_index = None;
+        let can_remove = self.logic.labor_roles.len() > 1;
+        Grid::new("labor_roles")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("Role");
+                ui.label("Hourly rate");
+                ui.end_row();
+                for (i, role) in self.logic.labor_roles.iter_mut().enumerate() {
+                    ui.text_edit_singleline(&mut role.name);
+                    money_input(ui, &mut role.hourly_rate, locale, 0.1);
+                    if can_remove && ui.button("🗑️").clicked() {
+                        remove_index = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_index {
+            self.logic.labor_roles.remove(index);
+            // Keep phases and operations pointing at the same roles after the removal
+            let roles = self
+                .logic
+                .job
+                .labor
+                .iter_mut()
+                .map(|phase| &mut phase.role)
+                .chain(self.logic.post_processing_operations.iter_mut().map(|op| &mut op.role));
+            for role in roles {
+                if *role > index {
+                    *role -= 1;
+                } else if *role == index {
+                    *role = 0;
+                }
+            }
+        }
+        if ui.button("+ Add Role").clicked() {
+            self.logic.labor_roles.push(LaborRole {
+                name: "New role".to_string(),
+                hourly_rate: Money::from_f32(15.0, self.logic.exchange_rates.base),
+            });
+        }
+    }
+
+    /// Shows which cost stages of the pricing pipeline the markup applies to.
+    fn pricing_pipeline_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("Markup applies to:");
+        for entry in &mut self.logic.pricing_pipeline {
+            ui.checkbox(&mut entry.markup_applies, entry.stage.label());
+        }
+        ui.label("Markup is followed by discounts, setup fee and minimum order, then fees, rounding and tax.");
+    }
+
+    /// Shows the editor for the post-processing operation catalog.
+    fn post_processing_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let mut remove_index = None;
+        let logic = &mut self.logic;
+        Grid::new("post_processing_operations")
+            .num_columns(6)
+            .show(ui, |ui| {
+                ui.label("Operation");
+                ui.label("Minutes per part");
+                ui.label("Role");
+                ui.label("Consumables");
+                ui.label("Cost per part");
+                ui.end_row();
+                for (i, operation) in logic.post_processing_operations.iter_mut().enumerate() {
+                    ui.text_edit_singleline(&mut operation.name);
+                    ui.add(drag_value(&mut operation.minutes_per_part, locale).speed(0.5).range(0.0..=f32::MAX));
+                    let selected = logic
+                        .labor_roles
+                        .get(operation.role)
+                        .or_else(|| logic.labor_roles.first())
+                        .map(|role| role.name.clone())
+                        .unwrap_or_default();
+                    egui::ComboBox::from_id_salt(format!("operation_role_{}", i))
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for (j, role) in logic.labor_roles.iter().enumerate() {
+                                ui.selectable_value(&mut operation.role, j, &role.name);
+                            }
+                        });
+                    ui.text_edit_singleline(&mut operation.consumables);
+                    money_input(ui, &mut operation.consumable_cost_per_part, locale, 0.01);
+                    if ui.button("🗑️").clicked() {
+                        remove_index = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_index {
+            logic.post_processing_operations.remove(index);
+            // Drop the job's steps using the operation and renumber the rest
+            logic.job.post_processing.retain(|step| step.operation != index);
+            for step in &mut logic.job.post_processing {
+                if step.operation > index {
+                    step.operation -= 1;
+                }
+            }
+        }
+        if ui.button("+ Add Operation").clicked() {
+            logic.post_processing_operations.push(PostProcessOperation {
+                name: "New operation".to_string(),
+                role: 0,
+                minutes_per_part: 0.0,
+                consumables: String::new(),
+                consumable_cost_per_part: Money::zero(logic.exchange_rates.base),
+            });
+        }
+    }
+
+    /// Shows the editor for the named tax rates.
+    fn tax_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let mut remove_index = None;
+        Grid::new("tax_rates")
+            .num_columns(4)
+            .show(ui, |ui| {
+                ui.label("Apply");
+                ui.label("Name");
+                ui.label("Rate (%)");
+                ui.end_row();
+                for (i, tax) in self.logic.tax_rates.iter_mut().enumerate() {
+                    ui.checkbox(&mut tax.enabled, "");
+                    ui.text_edit_singleline(&mut tax.name);
+                    ui.add(drag_value(&mut tax.rate_percentage, locale).speed(0.1).range(0.0..=100.0));
+                    if ui.button("🗑️").clicked() {
+                        remove_index = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_index {
+            self.logic.tax_rates.remove(index);
+        }
+        if ui.button("+ Add Tax Rate").clicked() {
+            self.logic.tax_rates.push(TaxRate {
+                name: "Tax".to_string(),
+                rate_percentage: 0.0,
+                enabled: true,
+            });
+        }
+    }
+
+    /// Shows the editor for the quantity discount tiers.
+    fn discount_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let mut remove_index = None;
+        Grid::new("discount_tiers")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("From quantity");
+                ui.label("Discount (%)");
+                ui.end_row();
+                for (i, tier) in self.logic.discount_tiers.iter_mut().enumerate() {
+                    ui.add(drag_value(&mut tier.min_quantity, locale).range(1..=MAX_QUANTITY));
+                    ui.add(drag_value(&mut tier.discount_percentage, locale).speed(0.5).range(0.0..=100.0));
+                    if ui.button("🗑️").clicked() {
+                        remove_index = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_index {
+            self.logic.discount_tiers.remove(index);
+        }
+        if self.logic.discount_tiers.is_empty() {
+            ui.label("No tiers: quotes are not discounted.");
+        }
+        if ui.button("+ Add Tier").clicked() {
+            self.logic.discount_tiers.push(DiscountTier { min_quantity: 10, discount_percentage: 5.0 });
+        }
+    }
+
+    /// Shows the surcharge and nozzle wear settings for each additive category.
+    fn additive_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let symbol = self.logic.exchange_rates.base.symbol();
+        let rate_unit = self.logic.units.spool_weight.rate_unit();
+        let per_rate_unit = rate_unit.grams() / 1000.0;
+        Grid::new("additive_settings")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("Additive");
+                ui.label(format!("Surcharge ({}/{})", symbol, rate_unit.label()));
+                ui.label("Nozzle wear factor");
+                ui.end_row();
+                for additive in Additive::ALL.into_iter().skip(1) {
+                    if let Some(settings) = self.logic.additive_settings.get_mut(&additive) {
+                        ui.label(additive.label());
+                        let mut surcharge = settings.surcharge_per_kg * per_rate_unit;
+                        if ui.add(drag_value(&mut surcharge, locale).speed(0.1)).changed() {
+                            settings.surcharge_per_kg = surcharge / per_rate_unit;
+                        }
+                        ui.add(
+                            drag_value(&mut settings.nozzle_wear_factor, locale)
+                                .speed(0.1)
+                                .range(1.0..=20.0),
+                        );
+                        ui.end_row();
+                    }
+                }
+            });
+    }
+
+    /// Shows the flushing volume matrix and tool change counts for multi-color jobs.
+    fn flush_plan_editor(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let job = &mut self.logic.job;
+        let count = job.filaments.len();
+        ui.checkbox(&mut job.flush.enabled, "Estimate purge from flushing volumes");
+
+        ui.label("Flushing volume (mm³), from row to column:");
+        Grid::new("flush_volumes").show(ui, |ui| {
+            ui.label("");
+            for to in 0..count {
+                ui.label(format!("#{}", to + 1));
+            }
+            ui.end_row();
+            for from in 0..count {
+                ui.label(format!("#{}", from + 1));
+                for to in 0..count {
+                    if from == to {
+                        ui.label("-");
+                    } else {
+                        ui.add(
+                            drag_value(&mut job.flush.volumes[from][to], locale)
+                                .speed(5.0)
+                                .range(0.0..=f32::MAX),
+                        );
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.label("Tool changes, from row to column:");
+        Grid::new("flush_changes").show(ui, |ui| {
+            ui.label("");
+            for to in 0..count {
+                ui.label(format!("#{}", to + 1));
+            }
+            ui.end_row();
+            for from in 0..count {
+                ui.label(format!("#{}", from + 1));
+                for to in 0..count {
+                    if from == to {
+                        ui.label("-");
+                    } else {
+                        ui.add(drag_value(&mut job.flush.changes[from][to], locale));
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Tool change sequence:");
+            ui.text_edit_singleline(&mut self.flush_sequence)
+                .on_hover_text("Filament numbers in print order, e.g. 1, 2, 1, 3");
+            if ui.button("Apply").clicked() {
+                self.flush_sequence_invalid = !job.flush.set_changes_from_sequence(&self.flush_sequence);
+            }
+        });
+        if self.flush_sequence_invalid {
+            ui.colored_label(egui::Color32::RED, format!("Use filament numbers from 1 to {}.", count));
+        }
+
+        let unit = self.logic.units.filament_weight;
+        for (i, grams) in job.flush_purge_weights().iter().enumerate() {
+            ui.label(format!(
+                "Filament #{} flushed: {} {}",
+                i + 1,
+                locale.format_input(unit.convert(*grams) as f64, unit.decimals()..=unit.decimals()),
+                unit.label()
+            ));
+        }
+    }
+
+    /// Shows the editor for the labor phases of the job.
+    fn labor_editor(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let mut remove_index = None;
+        let logic = &mut self.logic;
+        Grid::new("labor_phases")
+            .num_columns(5)
+            .show(ui, |ui| {
+                ui.label("Phase");
+                ui.label("Minutes");
+                ui.label("Applies");
+                ui.label("Role");
+                ui.end_row();
+                for (i, phase) in logic.job.labor.iter_mut().enumerate() {
+                    ui.text_edit_singleline(&mut phase.name);
+                    ui.add(drag_value(&mut phase.minutes, locale).speed(0.5).range(0.0..=f32::MAX));
+                    egui::ComboBox::from_id_salt(format!("labor_scope_{}", i))
+                        .selected_text(phase.scope.label())
+                        .show_ui(ui, |ui| {
+                            for scope in LaborScope::ALL {
+                                ui.selectable_value(&mut phase.scope, scope, scope.label());
+                            }
+                        });
+                    let selected = logic
+                        .labor_roles
+                        .get(phase.role)
+                        .or_else(|| logic.labor_roles.first())
+                        .map(|role| role.name.clone())
+                        .unwrap_or_default();
+                    egui::ComboBox::from_id_salt(format!("labor_role_{}", i))
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for (j, role) in logic.labor_roles.iter().enumerate() {
+                                ui.selectable_value(&mut phase.role, j, &role.name);
+                            }
+                        });
+                    if ui.button("🗑️").clicked() {
+                        remove_index = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_index {
+            logic.job.labor.remove(index);
+        }
+        if ui.button("+ Add Phase").clicked() {
+            logic.job.labor.push(LaborPhase {
+                name: "New phase".to_string(),
+                role: 0,
+                minutes: 0.0,
+                scope: LaborScope::Job,
+            });
+        }
+        ui.label(format!("Labor time: {:.2} h", logic.job.labor_hours()));
+    }
+
+    /// Shows the post-processing operations attached to the job.
+    fn post_processing_editor(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let mut remove_index = None;
+        let logic = &mut self.logic;
+        Grid::new("post_processing_steps")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("Operation");
+                ui.label("Times per part");
+                ui.end_row();
+                for (i, step) in logic.job.post_processing.iter_mut().enumerate() {
+                    let selected = logic
+                        .post_processing_operations
+                        .get(step.operation)
+                        .map(|operation| operation.name.clone())
+                        .unwrap_or_default();
+                    egui::ComboBox::from_id_salt(format!("post_processing_step_{}", i))
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for (j, operation) in logic.post_processing_operations.iter().enumerate() {
+                                ui.selectable_value(&mut step.operation, j, &operation.name);
+                            }
+                        });
+                    ui.add(drag_value(&mut step.repeats, locale).range(1..=1000));
+                    if ui.button("🗑️").clicked() {
+                        remove_index = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove_index {
+            logic.job.post_processing.remove(index);
+        }
+        if !logic.post_processing_operations.is_empty() && ui.button("+ Add Operation").clicked() {
+            logic.job.post_processing.push(PostProcessStep { operation: 0, repeats: 1 });
+        }
+    }
+
+    /// Shows the editor for the printer profiles.
+    fn printer_settings(&mut self, ui: &mut egui::Ui) {
+        let locale = self.logic.locale;
+        let mut remove_index = None;
+        let can_remove = self.logic.printers.len() > 1;
+        let base = self.logic.exchange_rates.base;
+        for (i, printer) in self.logic.printers.iter_mut().enumerate() {
+            ui.group(|ui| {
+                Grid::new(format!("printer_profile_{}", i))
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut printer.name);
+                        ui.end_row();
+                        ui.label("Purchase price:");
+                        money_input(ui, &mut printer.purchase_price, locale, 1.0);
+                        ui.end_row();
+                        ui.label("Residual value:");
+                        money_input(ui, &mut printer.residual_value, locale, 1.0);
+                        ui.end_row();
+                        ui.label("Expected lifetime (hours):");
+                        ui.add(drag_value(&mut printer.lifetime_hours, locale).speed(10.0));
+                        ui.end_row();
+                        ui.label("Yearly maintenance budget:");
+                        money_input(ui, &mut printer.yearly_maintenance, locale, 1.0);
+                        ui.end_row();
+                        ui.label("Print hours per year:");
+                        ui.add(drag_value(&mut printer.yearly_print_hours, locale).speed(10.0));
+                        ui.end_row();
+                        ui.label("Failure rate (% per 10 hours):");
+                        ui.add(drag_value(&mut printer.failure_rate, locale).speed(0.1).range(0.0..=90.0));
+                        ui.end_row();
+                    });
+                ui.label(format!(
+                    "Wear and tear: {} per print hour",
+                    locale.format_money(printer.wear_cost(1.0))
+                ));
+
+                // Power draw while heating up, printing and idling
+                ui.collapsing("Power profile", |ui| {
+                    let power = &mut printer.power;
+                    Grid::new(format!("power_profile_{}", i))
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("Base draw while printing (W):");
+                            ui.add(drag_value(&mut power.base_watts, locale).speed(1.0).range(0.0..=f32::MAX));
+                            ui.end_row();
+                            ui.label("Bed draw (W per °C):");
+                            ui.add(drag_value(&mut power.bed_watts_per_degree, locale).speed(0.01).range(0.0..=f32::MAX));
+                            ui.end_row();
+                            ui.label("Hotend draw (W per °C):");
+                            ui.add(drag_value(&mut power.hotend_watts_per_degree, locale).speed(0.01).range(0.0..=f32::MAX));
+                            ui.end_row();
+                            ui.label("Bed warm-up (Wh per °C):");
+                            ui.add(drag_value(&mut power.bed_warmup_wh_per_degree, locale).speed(0.01).range(0.0..=f32::MAX));
+                            ui.end_row();
+                            ui.label("Hotend warm-up (Wh per °C):");
+                            ui.add(drag_value(&mut power.hotend_warmup_wh_per_degree, locale).speed(0.01).range(0.0..=f32::MAX));
+                            ui.end_row();
+                            ui.label("Chamber heater (W):");
+                            ui.add(drag_value(&mut power.chamber_heater_watts, locale).speed(1.0).range(0.0..=f32::MAX));
+                            ui.end_row();
+                            ui.label("Standby draw (W):");
+                            ui.add(drag_value(&mut power.standby_watts, locale).speed(0.5).range(0.0..=f32::MAX));
+                            ui.end_row();
+                            ui.label("Idle time between plates (min):");
+                            ui.add(drag_value(&mut power.idle_minutes_per_plate, locale).speed(1.0).range(0.0..=f32::MAX));
+                            ui.end_row();
+                        });
+                });
+
+                // Consumable parts of this printer
+                ui.collapsing(format!("Consumables ({})", printer.consumables.len()), |ui| {
+                    let mut remove_part = None;
+                    Grid::new(format!("consumables_{}", i))
+                        .num_columns(5)
+                        .show(ui, |ui| {
+                            ui.label("Part");
+                            ui.label("Cost");
+                            ui.label("Life (hours)");
+                            ui.label("Abrasion-sensitive");
+                            ui.end_row();
+                            for (j, part) in printer.consumables.iter_mut().enumerate() {
+                                ui.text_edit_singleline(&mut part.name);
+                                money_input(ui, &mut part.cost, locale, 0.1);
+                                ui.add(drag_value(&mut part.service_life_hours, locale).speed(10.0));
+                                ui.checkbox(&mut part.abrasion_sensitive, "");
+                                if ui.button("🗑️").clicked() {
+                                    remove_part = Some(j);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    if let Some(index) = remove_part {
+                        printer.consumables.remove(index);
+                    }
+                    if ui.button("+ Add Part").clicked() {
+                        printer.consumables.push(ConsumablePart {
+                            cost: Money::zero(base),
+                            ..ConsumablePart::default()
+                        });
+                    }
+                });
+
+                if can_remove && ui.button(egui::RichText::new("🗑️").size(18.0)).clicked() {
+                    remove_index = Some(i);
+                }
+            });
+        }
+
+        if let Some(index) = remove_index {
+            self.logic.printers.remove(index);
+            shift_selection(&mut self.logic.job.printer, index);
+        }
+        if ui.button("+ Add Printer").clicked() {
+            // Enter the default prices in the base currency
+            let mut printer = PrinterProfile::default();
+            for amount in [
+                &mut printer.purchase_price,
+                &mut printer.residual_value,
+                &mut printer.yearly_maintenance,
+            ] {
+                *amount = self.logic.convert(*amount, base);
+            }
+            for part in &mut printer.consumables {
+                part.cost = self.logic.convert(part.cost, base);
+            }
+            self.logic.printers.push(printer);
+        }
+    }
+}
+
+/// Keeps a selected index pointing at the same entry after the entry at `removed` is
+/// deleted, falling back to the first entry when the selected one itself was deleted.
+fn shift_selection(selected: &mut usize, removed: usize) {
+    if *selected > removed {
+        *selected -= 1;
+    } else if *selected == removed {
+        *selected = 0;
+    }
+}
+
+/// Shows a drag value for a weight stored in grams, entered in the given unit.
+fn weight_input(ui: &mut egui::Ui, grams: &mut f32, unit: WeightUnit, locale: NumberLocale) {
+    let mut value = unit.convert(*grams);
+    if ui.add(drag_value(&mut value, locale).speed(unit.step()).range(0.0..=f32::MAX)).changed() {
+        *grams = unit.grams_of(value);
+    }
+}
+
+/// Shows an input for an amount of money in major units, with the decimals of its currency.
+fn money_input(ui: &mut egui::Ui, amount: &mut Money, locale: NumberLocale, speed: f64) {
+    let currency = amount.currency();
+    let decimals = currency.minor_units() as usize;
+    let mut value = amount.to_f64();
+    let input = drag_value(&mut value, locale)
+        .speed(speed)
+        .range(0.0..=f64::MAX)
+        .min_decimals(decimals)
+        .max_decimals(decimals);
+    if ui.add(input).changed() {
+        *amount = Money::from_f64(value, currency);
+    }
+}
+
+/// Creates a drag value that shows and accepts numbers in the locale's format, so a
+/// decimal comma can be typed where the locale uses one.
+fn drag_value<'a, Num: egui::emath::Numeric>(value: &'a mut Num, locale: NumberLocale) -> egui::DragValue<'a> {
+    egui::DragValue::new(value)
+        .custom_formatter(move |number, decimals| locale.format_input(number, decimals))
+        .custom_parser(move |text| locale.parse_number(text))
+}
+
 // Load the logo
 pub fn load_logo(cc: &eframe::CreationContext<'_>) -> Option<TextureHandle> {
     let bytes = include_bytes!("../assets/logo.png");
@@ -0,0 +1,179 @@
+/// A unit that weights are entered and shown in. Weights are stored in grams.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeightUnit {
+    Gram,
+    Kilogram,
+    Ounce,
+    Pound,
+}
+
+impl WeightUnit {
+    pub const ALL: [WeightUnit; 4] = [
+        WeightUnit::Gram,
+        WeightUnit::Kilogram,
+        WeightUnit::Ounce,
+        WeightUnit::Pound,
+    ];
+
+    /// Returns the unit symbol.
+    pub fn label(&self) -> &'static str {
+        match self {
+            WeightUnit::Gram => "g",
+            WeightUnit::Kilogram => "kg",
+            WeightUnit::Ounce => "oz",
+            WeightUnit::Pound => "lb",
+        }
+    }
+
+    /// Returns the grams in one unit.
+    pub fn grams(&self) -> f32 {
+        match self {
+            WeightUnit::Gram => 1.0,
+            WeightUnit::Kilogram => 1000.0,
+            WeightUnit::Ounce => 28.349_523,
+            WeightUnit::Pound => 453.592_37,
+        }
+    }
+
+    /// Converts grams to this unit.
+    pub fn convert(&self, grams: f32) -> f32 {
+        grams / self.grams()
+    }
+
+    /// Returns the grams of a value in this unit.
+    pub fn grams_of(&self, value: f32) -> f32 {
+        value * self.grams()
+    }
+
+    /// Returns the unit that prices per weight are quoted in alongside this unit.
+    pub fn rate_unit(&self) -> WeightUnit {
+        match self {
+            WeightUnit::Gram | WeightUnit::Kilogram => WeightUnit::Kilogram,
+            WeightUnit::Ounce | WeightUnit::Pound => WeightUnit::Pound,
+        }
+    }
+
+    /// Returns the decimals that show a weight in this unit to about a tenth of a gram.
+    pub fn decimals(&self) -> usize {
+        match self {
+            WeightUnit::Gram => 1,
+            WeightUnit::Kilogram => 4,
+            WeightUnit::Ounce => 3,
+            WeightUnit::Pound => 4,
+        }
+    }
+
+    /// Returns a drag step of roughly one gram in this unit.
+    pub fn step(&self) -> f64 {
+        match self {
+            WeightUnit::Gram => 1.0,
+            WeightUnit::Kilogram => 0.001,
+            WeightUnit::Ounce => 0.05,
+            WeightUnit::Pound => 0.002,
+        }
+    }
+}
+
+/// A unit that filament lengths are entered and shown in. Lengths are stored in millimeters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthUnit {
+    Millimeter,
+    Meter,
+    Foot,
+}
+
+impl LengthUnit {
+    pub const ALL: [LengthUnit; 3] = [LengthUnit::Millimeter, LengthUnit::Meter, LengthUnit::Foot];
+
+    /// Returns the unit symbol.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LengthUnit::Millimeter => "mm",
+            LengthUnit::Meter => "m",
+            LengthUnit::Foot => "ft",
+        }
+    }
+
+    /// Returns the millimeters in one unit.
+    pub fn millimeters(&self) -> f32 {
+        match self {
+            LengthUnit::Millimeter => 1.0,
+            LengthUnit::Meter => 1000.0,
+            LengthUnit::Foot => 304.8,
+        }
+    }
+
+    /// Converts millimeters to this unit.
+    pub fn convert(&self, millimeters: f32) -> f32 {
+        millimeters / self.millimeters()
+    }
+
+    /// Returns the millimeters of a value in this unit.
+    pub fn millimeters_of(&self, value: f32) -> f32 {
+        value * self.millimeters()
+    }
+
+    /// Returns a drag step of roughly ten centimeters in this unit.
+    pub fn step(&self) -> f64 {
+        match self {
+            LengthUnit::Millimeter => 100.0,
+            LengthUnit::Meter => 0.1,
+            LengthUnit::Foot => 0.5,
+        }
+    }
+}
+
+/// How the filament used by a job is entered. It is always stored as a weight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilamentQuantity {
+    Weight,
+    Length,
+    Volume,
+}
+
+impl FilamentQuantity {
+    pub const ALL: [FilamentQuantity; 3] = [
+        FilamentQuantity::Weight,
+        FilamentQuantity::Length,
+        FilamentQuantity::Volume,
+    ];
+
+    /// Returns the display name of the quantity.
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilamentQuantity::Weight => "Weight",
+            FilamentQuantity::Length => "Length",
+            FilamentQuantity::Volume => "Volume",
+        }
+    }
+}
+
+/// The units each kind of quantity is entered in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnitSettings {
+    pub spool_weight: WeightUnit,    // Roll weights
+    pub filament_weight: WeightUnit, // Filament used, purge and flushing per plate
+    pub filament_length: LengthUnit, // Filament length reported by slicers
+}
+
+impl UnitSettings {
+    /// Grams, kilograms and meters.
+    pub const METRIC: UnitSettings = UnitSettings {
+        spool_weight: WeightUnit::Gram,
+        filament_weight: WeightUnit::Gram,
+        filament_length: LengthUnit::Meter,
+    };
+
+    /// Pounds for spools, ounces for filament and feet.
+    pub const IMPERIAL: UnitSettings = UnitSettings {
+        spool_weight: WeightUnit::Pound,
+        filament_weight: WeightUnit::Ounce,
+        filament_length: LengthUnit::Foot,
+    };
+}
+
+impl Default for UnitSettings {
+    fn default() -> Self {
+        UnitSettings::METRIC
+    }
+}